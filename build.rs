@@ -0,0 +1,175 @@
+//! Generates the list of solution modules included by the main binary.
+//! See `template::registry` for how these are executed in-process.
+use std::{
+    env,
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+};
+
+// the example parser is shared with the library, which is not available to build scripts.
+#[path = "src/template/examples.rs"]
+#[allow(dead_code)]
+mod examples;
+
+fn main() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let bin_dir = Path::new(&manifest_dir).join("src").join("bin");
+    println!("cargo:rerun-if-changed={}", bin_dir.display());
+
+    let mut days: Vec<(String, String)> = fs::read_dir(&bin_dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter(|path| path.extension().is_some_and(|ext| ext == "rs"))
+                .filter_map(|path| {
+                    // solutions are named `{year}-{day}.rs`.
+                    let stem = path.file_stem()?.to_str()?;
+                    let (year, day) = stem.split_once('-')?;
+                    let day: u8 = day.parse().ok()?;
+                    let year: u16 = year.parse().ok()?;
+                    (year >= 2015 && (1..=25).contains(&day))
+                        .then(|| (stem.replace('-', "_"), path.display().to_string()))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    days.sort();
+
+    // NOTE: every solution declares its own global allocator when profiling with dhat,
+    // so the registry is left empty and days are run as separate binaries instead.
+    // Solution tests already run as part of each binary, so they are left out of the main binary's tests.
+    // Without the `in-process` feature, the registry is left empty too, so that a day which does
+    // not compile yet cannot break the commands of every other day.
+    let in_process = env::var_os("CARGO_FEATURE_IN_PROCESS").is_some();
+    let registered = if in_process { days.as_slice() } else { &[] };
+    let mut out = String::new();
+
+    for (day, path) in registered {
+        writeln!(out, "#[cfg(not(any(test, feature = \"dhat-heap\")))]").unwrap();
+        writeln!(out, "#[path = {path:?}]").unwrap();
+        // solutions are written as crate roots, so visibility-based lints don't apply here.
+        writeln!(out, "#[allow(dead_code, clippy::unnecessary_wraps)]").unwrap();
+        writeln!(out, "mod day_{day};").unwrap();
+    }
+
+    writeln!(out, "#[cfg(not(any(test, feature = \"dhat-heap\")))]").unwrap();
+    writeln!(
+        out,
+        "pub const SOLUTIONS: &[advent_of_code::template::registry::Solution] = &["
+    )
+    .unwrap();
+    for (day, _) in registered {
+        writeln!(out, "    day_{day}::SOLUTION,").unwrap();
+    }
+    writeln!(out, "];").unwrap();
+
+    writeln!(out, "#[cfg(any(test, feature = \"dhat-heap\"))]").unwrap();
+    writeln!(
+        out,
+        "pub const SOLUTIONS: &[advent_of_code::template::registry::Solution] = &[];"
+    )
+    .unwrap();
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("solutions.rs"), out).unwrap();
+
+    write_example_tests(Path::new(&manifest_dir), Path::new(&out_dir), &days);
+}
+
+/// Generates the tests included by `example_tests!` for every solution, one per example file
+/// and part with an expected answer in the file's header.
+fn write_example_tests(manifest_dir: &Path, out_dir: &Path, days: &[(String, String)]) {
+    let tests_dir = out_dir.join("example_tests");
+    fs::create_dir_all(&tests_dir).unwrap();
+
+    let config_path = manifest_dir.join("aoc.toml");
+    println!("cargo:rerun-if-changed={}", config_path.display());
+    let config = fs::read_to_string(&config_path).unwrap_or_default();
+
+    for (module, _) in days {
+        let (year, day) = module.split_once('_').unwrap();
+        let examples_dir = examples_dir(manifest_dir, &config, year);
+        println!("cargo:rerun-if-changed={}", examples_dir.display());
+
+        let mut files: Vec<String> = fs::read_dir(&examples_dir)
+            .map(|entries| {
+                entries
+                    .filter_map(Result::ok)
+                    .filter_map(|entry| entry.file_name().into_string().ok())
+                    .filter(|name| {
+                        let stem = name.strip_suffix(".txt").unwrap_or_default();
+                        stem == day || stem.strip_prefix(day).is_some_and(|s| s.starts_with('-'))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        files.sort();
+
+        let mut out = String::new();
+
+        for file in files {
+            let path = examples_dir.join(&file);
+            println!("cargo:rerun-if-changed={}", path.display());
+
+            let text = fs::read_to_string(&path).unwrap_or_default();
+            let test_name: String = file
+                .trim_end_matches(".txt")
+                .chars()
+                .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+                .collect();
+
+            for part in examples::declared_parts(&text) {
+                writeln!(out, "#[test]").unwrap();
+                writeln!(out, "fn example_{test_name}_part_{part}() {{").unwrap();
+                writeln!(
+                    out,
+                    "    ::advent_of_code::template::runner::check_example(&super::SOLUTION, {file:?}, {part});"
+                )
+                .unwrap();
+                writeln!(out, "}}").unwrap();
+            }
+        }
+
+        let bin_name = module.replace('_', "-");
+        fs::write(tests_dir.join(format!("{bin_name}.rs")), out).unwrap();
+    }
+}
+
+/// Directory of the examples of a year, following `paths.examples` and `paths.data` of
+/// `aoc.toml` like `template::config` does.
+#[allow(clippy::literal_string_with_formatting_args)]
+fn examples_dir(manifest_dir: &Path, config: &str, year: &str) -> PathBuf {
+    let mut table = String::new();
+    let mut data = "data/{year}".to_string();
+    let mut examples = None;
+
+    for line in config.lines().map(str::trim) {
+        if let Some(header) = line.strip_prefix('[') {
+            let name = header.split(']').next().unwrap_or_default().trim();
+            table = format!("{name}.");
+            continue;
+        }
+
+        let Some((key, value)) = line.split_once('=').filter(|_| !line.starts_with('#')) else {
+            continue;
+        };
+
+        // values are quoted strings, possibly followed by a comment.
+        let value = value
+            .trim()
+            .strip_prefix('"')
+            .and_then(|v| v.split('"').next());
+        match (format!("{table}{}", key.trim()).as_str(), value) {
+            ("paths.data", Some(value)) => data = value.to_string(),
+            ("paths.examples", Some(value)) => examples = Some(value.to_string()),
+            _ => {}
+        }
+    }
+
+    let folder = examples.unwrap_or_else(|| format!("{data}/examples"));
+    manifest_dir.join(folder.replace("{year}", year))
+}
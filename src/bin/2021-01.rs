@@ -1,4 +1,5 @@
-advent_of_code::solution!(1);
+advent_of_code::solution!(2021, 1);
+advent_of_code::example_tests!();
 
 fn count_increases(mut values: impl Iterator<Item = u32>) -> Option<u32> {
     let mut prev = values.next()?;
@@ -59,13 +60,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(7));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(5));
     }
 }
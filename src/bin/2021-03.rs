@@ -1,7 +1,7 @@
 use std::num::ParseIntError;
 use std::str::FromStr;
 
-advent_of_code::solution!(3);
+advent_of_code::solution!(2021, 3);
 
 const DIGITS: usize = if cfg!(test) { 5 } else { 12 };
 const MAX_VALUE: usize = 1 << DIGITS;
@@ -159,7 +159,7 @@ mod tests {
     #[test]
     fn test_parse_input() {
         assert_eq!(
-            NumberSet::from_str(&advent_of_code::template::read_file("examples", DAY)),
+            NumberSet::from_str(&advent_of_code::template::read_file("examples", YEAR, DAY)),
             Ok(example_number_set()),
         );
     }
@@ -176,7 +176,7 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(198));
     }
 
@@ -242,7 +242,7 @@ mod tests {
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(230));
     }
 }
@@ -1,6 +1,6 @@
 use std::str::FromStr;
 
-advent_of_code::solution!(4);
+advent_of_code::solution!(2021, 4);
 
 #[derive(Debug, PartialEq)]
 struct BitSet(u128);
@@ -168,6 +168,7 @@ pub fn part_two(input: &str) -> Option<u32> {
 }
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison, clippy::unreadable_literal)]
 mod tests {
     use super::*;
 
@@ -233,7 +234,7 @@ mod tests {
     #[test]
     fn test_parse_input() {
         assert_eq!(
-            BingoGame::from_str(&advent_of_code::template::read_file("examples", DAY)),
+            BingoGame::from_str(&advent_of_code::template::read_file("examples", YEAR, DAY)),
             Ok(example_bingo_game()),
         );
     }
@@ -270,7 +271,7 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(4512));
     }
 
@@ -282,7 +283,7 @@ mod tests {
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(1924));
     }
 }
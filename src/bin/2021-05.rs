@@ -2,7 +2,9 @@ use std::cmp::Ordering;
 use std::iter::{empty, successors};
 use std::str::FromStr;
 
-advent_of_code::solution!(5);
+use advent_of_code::template::ParseError;
+
+advent_of_code::solution!(2021, 5, parse = parse);
 
 const GRID_SIZE: usize = 1000;
 
@@ -79,7 +81,7 @@ impl Vent {
 }
 
 #[derive(Debug, PartialEq)]
-struct VentSystem {
+pub struct VentSystem {
     vents: Vec<Vent>,
 }
 
@@ -132,13 +134,15 @@ impl FromStr for Vent {
 }
 
 impl FromStr for VentSystem {
-    type Err = ParseVentError;
+    type Err = ParseError;
 
     fn from_str(input: &str) -> Result<Self, Self::Err> {
         let mut vents = Vec::new();
 
-        for line in input.lines() {
-            let vent = Vent::from_str(line)?;
+        for (ix, line) in input.lines().enumerate() {
+            let vent = Vent::from_str(line).map_err(|_| {
+                ParseError::new(format!("expected `x1,y1 -> x2,y2`, got `{line}`")).at_line(ix + 1)
+            })?;
             vents.push(vent);
         }
 
@@ -146,14 +150,21 @@ impl FromStr for VentSystem {
     }
 }
 
+/// # Errors
+///
+/// Will return the line of the first vent that is not formatted as `x1,y1 -> x2,y2`.
+pub fn parse(input: &str) -> Result<VentSystem, ParseError> {
+    VentSystem::from_str(input)
+}
+
 #[must_use]
-pub fn part_one(input: &str) -> Option<usize> {
-    VentSystem::from_str(input).map_or(None, |system| Some(system.count_overlapping_points(false)))
+pub fn part_one(system: &VentSystem) -> Option<usize> {
+    Some(system.count_overlapping_points(false))
 }
 
 #[must_use]
-pub fn part_two(input: &str) -> Option<usize> {
-    VentSystem::from_str(input).map_or(None, |system| Some(system.count_overlapping_points(true)))
+pub fn part_two(system: &VentSystem) -> Option<usize> {
+    Some(system.count_overlapping_points(true))
 }
 
 #[cfg(test)]
@@ -210,11 +221,17 @@ mod tests {
     #[test]
     fn test_parse_input() {
         assert_eq!(
-            VentSystem::from_str(&advent_of_code::template::read_file("examples", DAY)),
+            VentSystem::from_str(&advent_of_code::template::read_file("examples", YEAR, DAY)),
             Ok(example_vent_system())
         );
     }
 
+    #[test]
+    fn test_parse_error_line() {
+        let error = VentSystem::from_str("0,9 -> 5,9\n8,0 -> 0\n").unwrap_err();
+        assert_eq!(error.line, Some(2));
+    }
+
     #[test]
     fn test_points_diagonal_not_allowed() {
         let diagonal = Vent {
@@ -262,7 +279,8 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let system = parse(&advent_of_code::template::read_file("examples", YEAR, DAY)).unwrap();
+        let result = part_one(&system);
         assert_eq!(result, Some(5));
     }
 
@@ -314,7 +332,8 @@ mod tests {
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let system = parse(&advent_of_code::template::read_file("examples", YEAR, DAY)).unwrap();
+        let result = part_two(&system);
         assert_eq!(result, Some(12));
     }
 }
@@ -1,6 +1,6 @@
 use std::str::FromStr;
 
-advent_of_code::solution!(6);
+advent_of_code::solution!(2021, 6);
 
 #[derive(Clone, Copy, Debug, PartialEq)]
 struct LanternFishPopulation {
@@ -76,7 +76,9 @@ mod tests {
     #[test]
     fn test_parse_input() {
         assert_eq!(
-            LanternFishPopulation::from_str(&advent_of_code::template::read_file("examples", DAY)),
+            LanternFishPopulation::from_str(&advent_of_code::template::read_file(
+                "examples", YEAR, DAY
+            )),
             Ok(example_lanternfish()),
         );
     }
@@ -124,13 +126,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(5934));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(26_984_457_539));
     }
 }
@@ -1,6 +1,6 @@
 use std::str::FromStr;
 
-advent_of_code::solution!(7);
+advent_of_code::solution!(2021, 7);
 
 #[derive(Debug, PartialEq)]
 struct SubmarinePositions {
@@ -98,7 +98,9 @@ mod tests {
     #[test]
     fn test_parse_input() {
         assert_eq!(
-            SubmarinePositions::from_str(&advent_of_code::template::read_file("examples", DAY)),
+            SubmarinePositions::from_str(&advent_of_code::template::read_file(
+                "examples", YEAR, DAY
+            )),
             Ok(example_positions()),
         );
     }
@@ -120,7 +122,7 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(37));
     }
 
@@ -139,7 +141,7 @@ mod tests {
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(168));
     }
 }
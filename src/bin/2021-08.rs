@@ -1,6 +1,6 @@
 use std::str::FromStr;
 
-advent_of_code::solution!(8);
+advent_of_code::solution!(2021, 8);
 
 #[derive(Debug, PartialEq)]
 struct Display {
@@ -16,6 +16,7 @@ impl Display {
             .count()
     }
 
+    #[allow(clippy::collapsible_match)]
     fn read_output(&self) -> usize {
         let mut decoded = [0; 10];
         let mut wires = [0; 7];
@@ -249,7 +250,7 @@ mod tests {
     #[test]
     fn test_parse_input() {
         assert_eq!(
-            DisplaySystem::from_str(&advent_of_code::template::read_file("examples", DAY)),
+            DisplaySystem::from_str(&advent_of_code::template::read_file("examples", YEAR, DAY)),
             Ok(example_display_system()),
         );
     }
@@ -274,7 +275,7 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(26));
     }
 
@@ -298,7 +299,7 @@ mod tests {
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(61_229));
     }
 }
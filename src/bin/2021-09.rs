@@ -1,7 +1,7 @@
 use std::collections::VecDeque;
 use std::str::FromStr;
 
-advent_of_code::solution!(9);
+advent_of_code::solution!(2021, 9);
 
 const GRID_SIZE: usize = if cfg!(test) { 10 } else { 100 };
 
@@ -186,7 +186,7 @@ mod tests {
     #[test]
     fn test_parse_input() {
         assert_eq!(
-            CaveMap::from_str(&advent_of_code::template::read_file("examples", DAY)),
+            CaveMap::from_str(&advent_of_code::template::read_file("examples", YEAR, DAY)),
             Ok(example_cave_map()),
         );
     }
@@ -252,7 +252,7 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(15));
     }
 
@@ -297,7 +297,7 @@ mod tests {
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(1134));
     }
 }
@@ -1,6 +1,6 @@
 use std::collections::VecDeque;
 
-advent_of_code::solution!(10);
+advent_of_code::solution!(2021, 10);
 
 #[derive(Debug, PartialEq)]
 enum Line {
@@ -112,13 +112,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(26_397));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(288_957));
     }
 }
@@ -1,7 +1,7 @@
 use std::collections::VecDeque;
 use std::str::FromStr;
 
-advent_of_code::solution!(11);
+advent_of_code::solution!(2021, 11);
 
 const GRID_SIZE: usize = 10;
 const GRID_SIZE_TOTAL: usize = GRID_SIZE * GRID_SIZE;
@@ -208,7 +208,7 @@ mod tests {
     #[test]
     fn test_parse_input() {
         assert_eq!(
-            OctopusGrid::from_str(&advent_of_code::template::read_file("examples", DAY)),
+            OctopusGrid::from_str(&advent_of_code::template::read_file("examples", YEAR, DAY)),
             Ok(example_grid()),
         );
     }
@@ -230,7 +230,7 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(1656));
     }
 
@@ -241,7 +241,7 @@ mod tests {
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(195));
     }
 }
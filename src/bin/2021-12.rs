@@ -1,7 +1,7 @@
 use std::collections::BTreeMap;
 use std::str::FromStr;
 
-advent_of_code::solution!(12);
+advent_of_code::solution!(2021, 12);
 
 const START: usize = 0;
 const END: usize = 1;
@@ -156,6 +156,7 @@ pub fn part_two(input: &str) -> Option<usize> {
 }
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod tests {
     use super::*;
 
@@ -182,7 +183,7 @@ mod tests {
     #[test]
     fn test_parse_input() {
         assert_eq!(
-            CaveSystem::from_str(&advent_of_code::template::read_file("examples", DAY)),
+            CaveSystem::from_str(&advent_of_code::template::read_file("examples", YEAR, DAY)),
             Ok(example_cave_system()),
         );
     }
@@ -224,13 +225,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(226));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(3509));
     }
 }
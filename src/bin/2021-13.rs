@@ -1,7 +1,7 @@
 use std::collections::BTreeSet;
 use std::str::FromStr;
 
-advent_of_code::solution!(13);
+advent_of_code::solution!(2021, 13);
 
 type Point = (usize, usize);
 
@@ -207,7 +207,7 @@ mod tests {
     #[test]
     fn test_parse_input() {
         assert_eq!(
-            Paper::from_str(&advent_of_code::template::read_file("examples", DAY)),
+            Paper::from_str(&advent_of_code::template::read_file("examples", YEAR, DAY)),
             Ok(example_paper()),
         );
     }
@@ -221,7 +221,7 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(17));
     }
 
@@ -242,7 +242,7 @@ mod tests {
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(example_output()));
     }
 }
@@ -1,6 +1,6 @@
 use std::str::FromStr;
 
-advent_of_code::solution!(14);
+advent_of_code::solution!(2021, 14);
 
 #[derive(Debug, PartialEq)]
 struct InsertionRule(usize, usize, usize);
@@ -209,7 +209,7 @@ mod tests {
     #[test]
     fn test_parse_input() {
         assert_eq!(
-            Polymer::from_str(&advent_of_code::template::read_file("examples", DAY)),
+            Polymer::from_str(&advent_of_code::template::read_file("examples", YEAR, DAY)),
             Ok(example_polymer()),
         );
     }
@@ -229,13 +229,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(1588));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(2_188_189_693_529));
     }
 }
@@ -1,7 +1,7 @@
 use std::collections::BTreeSet;
 use std::str::FromStr;
 
-advent_of_code::solution!(15);
+advent_of_code::solution!(2021, 15);
 
 #[derive(Clone, Copy, Debug, PartialEq)]
 enum Direction {
@@ -177,14 +177,14 @@ mod tests {
     #[test]
     fn test_parse_grid() {
         assert_eq!(
-            Grid::from_str(&advent_of_code::template::read_file("examples", DAY)),
+            Grid::from_str(&advent_of_code::template::read_file("examples", YEAR, DAY)),
             Ok(example_grid()),
         );
     }
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(40));
     }
 
@@ -208,7 +208,7 @@ mod tests {
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(315));
     }
 }
@@ -1,7 +1,7 @@
 use std::collections::VecDeque;
 use std::str::FromStr;
 
-advent_of_code::solution!(16);
+advent_of_code::solution!(2021, 16);
 
 #[derive(Debug, PartialEq)]
 enum Operation {
@@ -15,6 +15,7 @@ enum Operation {
 }
 
 #[derive(Debug, PartialEq)]
+#[allow(clippy::use_self)]
 enum Packet {
     Literal {
         version: usize,
@@ -218,20 +219,20 @@ mod tests {
     #[test]
     fn test_packet_from_str() {
         assert_eq!(
-            Packet::from_str(&advent_of_code::template::read_file("examples", DAY)),
+            Packet::from_str(&advent_of_code::template::read_file("examples", YEAR, DAY)),
             Ok(example_packet()),
         );
     }
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(11));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(9));
     }
 }
@@ -1,6 +1,6 @@
 use std::str::FromStr;
 
-advent_of_code::solution!(17);
+advent_of_code::solution!(2021, 17);
 
 #[derive(Debug, PartialEq)]
 struct TargetArea {
@@ -116,7 +116,7 @@ mod tests {
     #[test]
     fn test_parse_input() {
         assert_eq!(
-            TargetArea::from_str(&advent_of_code::template::read_file("examples", DAY)),
+            TargetArea::from_str(&advent_of_code::template::read_file("examples", YEAR, DAY)),
             Ok(example_target_area()),
         );
     }
@@ -139,13 +139,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(45));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(112));
     }
 }
@@ -1,7 +1,7 @@
 use std::ops::Add;
 use std::str::FromStr;
 
-advent_of_code::solution!(18);
+advent_of_code::solution!(2021, 18, parse = parse);
 
 const SNAILFISH_SIZE: usize = 63;
 const SNAILFISH_EXPLODE: usize = 15;
@@ -19,7 +19,7 @@ enum Node {
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
-struct SnailfishNumber {
+pub struct SnailfishNumber {
     nodes: [Node; SNAILFISH_SIZE],
 }
 
@@ -146,8 +146,8 @@ impl Add<Self> for SnailfishNumber {
     }
 }
 
-#[derive(Debug, PartialEq)]
-struct ParseSnailfishNumberError;
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseSnailfishNumberError;
 
 impl FromStr for SnailfishNumber {
     type Err = ParseSnailfishNumberError;
@@ -204,18 +204,21 @@ impl SnailfishNumber {
 }
 
 #[must_use]
-pub fn part_one(input: &str) -> Option<u32> {
-    let numbers = SnailfishNumber::read_from_input(input).ok()?;
+pub fn parse(input: &str) -> Option<Vec<SnailfishNumber>> {
+    SnailfishNumber::read_from_input(input).ok()
+}
+
+#[must_use]
+pub fn part_one(numbers: &[SnailfishNumber]) -> Option<u32> {
     numbers
-        .into_iter()
+        .iter()
+        .copied()
         .reduce(|acc, number| acc + number)
         .map(|number| number.magnitude(0))
 }
 
 #[must_use]
-pub fn part_two(input: &str) -> Option<u32> {
-    let numbers = SnailfishNumber::read_from_input(input).ok()?;
-
+pub fn part_two(numbers: &[SnailfishNumber]) -> Option<u32> {
     let mut best = None;
     for a in 0..numbers.len() {
         for b in 0..numbers.len() {
@@ -233,6 +236,7 @@ pub fn part_two(input: &str) -> Option<u32> {
 }
 
 #[cfg(test)]
+#[allow(clippy::too_many_lines)]
 mod tests {
     use super::*;
 
@@ -421,7 +425,9 @@ mod tests {
     #[test]
     fn test_read_snailfish_numbers() {
         assert_eq!(
-            SnailfishNumber::read_from_input(&advent_of_code::template::read_file("examples", DAY)),
+            SnailfishNumber::read_from_input(&advent_of_code::template::read_file(
+                "examples", YEAR, DAY
+            )),
             Ok(example_snailfish_numbers()),
         );
     }
@@ -535,13 +541,15 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let numbers = parse(&advent_of_code::template::read_file("examples", YEAR, DAY)).unwrap();
+        let result = part_one(&numbers);
         assert_eq!(result, Some(3488));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let numbers = parse(&advent_of_code::template::read_file("examples", YEAR, DAY)).unwrap();
+        let result = part_two(&numbers);
         assert_eq!(result, Some(3805));
     }
 }
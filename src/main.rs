@@ -1,14 +1,22 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use std::process;
+
+use advent_of_code::template::commands::{
+    all, crypt, download, examples, inputs, read, scaffold, solve, time, verify, CommandError,
+};
+use advent_of_code::template::Year;
 use args::{parse, AppArguments};
 
 #[cfg(feature = "today")]
 use advent_of_code::template::Day;
-#[cfg(feature = "today")]
-use std::process;
+
+mod solutions {
+    include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+}
 
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+    use advent_of_code::template::commands::time;
+    use advent_of_code::template::{config, Day, Limits, OutputFormat, Year};
+    use std::time::Duration;
 
     pub enum AppArguments {
         Download {
@@ -17,6 +25,10 @@ mod args {
         Read {
             day: Day,
         },
+        Examples {
+            day: Day,
+            overwrite: bool,
+        },
         Scaffold {
             day: Day,
             download: bool,
@@ -27,36 +39,127 @@ mod args {
             release: bool,
             dhat: bool,
             submit: Option<u8>,
+            input: Option<String>,
+            watch: bool,
+            format: OutputFormat,
+        },
+        Keygen,
+        Encrypt {
+            day: Option<Day>,
+        },
+        Decrypt {
+            day: Option<Day>,
+        },
+        Inputs {
+            day: Day,
+            dir: String,
+            release: bool,
+            check: bool,
+            format: OutputFormat,
         },
         All {
             release: bool,
+            jobs: usize,
+            limits: Limits,
+            format: OutputFormat,
         },
         Time {
             all: bool,
             day: Option<Day>,
             store: bool,
+            compare: Option<f64>,
+            heap: bool,
+            limits: Limits,
+            format: OutputFormat,
+        },
+        History {
+            day: Day,
+        },
+        Report {
+            path: String,
+        },
+        Verify {
+            day: Option<Day>,
+            record: bool,
         },
         #[cfg(feature = "today")]
         Today,
     }
 
-    pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
+    /// Parse the command and the event year it applies to.
+    pub fn parse() -> Result<(Year, AppArguments), Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
 
-        let app_args = match args.subcommand()?.as_deref() {
+        let subcommand = args.subcommand()?;
+
+        // `--config key=value` overrides `aoc.toml`, also for the solution bins run by commands.
+        config::add_overrides(&args.values_from_str::<_, String>("--config")?);
+        let config = config::init()?;
+
+        // every command is scoped to one year, defaulting to `year` from `aoc.toml` or `AOC_YEAR`
+        // from `.cargo/config.toml`.
+        let year = args
+            .opt_value_from_str("--year")?
+            .or_else(|| config.default_year())
+            .ok_or("no year given. Pass `--year` or set `year` in `aoc.toml`.")?;
+
+        let app_args = match subcommand.as_deref() {
             Some("all") => AppArguments::All {
                 release: args.contains("--release"),
+                jobs: args.opt_value_from_str("--jobs")?.unwrap_or(1),
+                limits: parse_limits(&mut args)?,
+                format: args.opt_value_from_str("--format")?.unwrap_or_default(),
+            },
+            Some("time") if args.contains("--history") => AppArguments::History {
+                day: args.free_from_str()?,
             },
             Some("time") => {
-                let all = args.contains("--all");
-                let store = args.contains("--store");
+                if let Some(path) = args.opt_value_from_str("--report")? {
+                    AppArguments::Report { path }
+                } else {
+                    let all = args.contains("--all");
+                    let store = args.contains("--store");
+                    let compare = args.contains("--compare");
+                    let heap = args.contains("--heap");
+                    let threshold = args
+                        .opt_value_from_str("--threshold")?
+                        .unwrap_or(time::DEFAULT_REGRESSION_THRESHOLD);
+                    let limits = parse_limits(&mut args)?;
+                    let format = args.opt_value_from_str("--format")?.unwrap_or_default();
+
+                    AppArguments::Time {
+                        all,
+                        day: args.opt_free_from_str()?,
+                        store,
+                        compare: compare.then_some(threshold),
+                        heap,
+                        limits,
+                        format,
+                    }
+                }
+            }
+            Some("verify") => {
+                let record = args.contains("--record");
 
-                AppArguments::Time {
-                    all,
-                    day: args.opt_free_from_str()?,
-                    store,
+                match args.opt_value_from_str("--inputs")? {
+                    Some(dir) => AppArguments::Inputs {
+                        day: args.free_from_str()?,
+                        dir,
+                        release: true,
+                        check: true,
+                        format: OutputFormat::Text,
+                    },
+                    None => AppArguments::Verify {
+                        day: args.opt_free_from_str()?,
+                        record,
+                    },
                 }
             }
+            Some("crypt") => parse_crypt(&mut args)?,
+            Some("examples") => AppArguments::Examples {
+                overwrite: args.contains("--overwrite"),
+                day: args.free_from_str()?,
+            },
             Some("download") => AppArguments::Download {
                 day: args.free_from_str()?,
             },
@@ -68,22 +171,11 @@ mod args {
                 download: args.contains("--download"),
                 overwrite: args.contains("--overwrite"),
             },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
-            },
+            Some("solve") => parse_solve(&mut args)?,
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
-            Some(x) => {
-                eprintln!("Unknown command: {x}");
-                process::exit(1);
-            }
-            None => {
-                eprintln!("No command specified.");
-                process::exit(1);
-            }
+            Some(x) => return Err(format!("unknown command `{x}`.").into()),
+            None => return Err("no command specified.".into()),
         };
 
         let remaining = args.finish();
@@ -91,54 +183,177 @@ mod args {
             eprintln!("Warning: unknown argument(s): {remaining:?}.");
         }
 
-        Ok(app_args)
+        Ok((year, app_args))
+    }
+
+    /// Parse `cargo solve`, which runs a day on several inputs with `--inputs`.
+    fn parse_solve(args: &mut pico_args::Arguments) -> Result<AppArguments, pico_args::Error> {
+        let release = args.contains("--release");
+        let format = args.opt_value_from_str("--format")?.unwrap_or_default();
+
+        Ok(match args.opt_value_from_str("--inputs")? {
+            Some(dir) => AppArguments::Inputs {
+                day: args.free_from_str()?,
+                dir,
+                release,
+                check: false,
+                format,
+            },
+            None => AppArguments::Solve {
+                day: args.free_from_str()?,
+                release,
+                submit: args.opt_value_from_str("--submit")?,
+                dhat: args.contains("--dhat"),
+                input: args.opt_value_from_str("--input")?,
+                watch: args.contains("--watch"),
+                format,
+            },
+        })
+    }
+
+    /// Parse the per-day limits of `cargo all` and `cargo time`, given in seconds and MiB.
+    fn parse_limits(args: &mut pico_args::Arguments) -> Result<Limits, pico_args::Error> {
+        let timeout: Option<f64> = args.opt_value_from_str("--timeout")?;
+        let memory: Option<u64> = args.opt_value_from_str("--memory")?;
+
+        Ok(Limits {
+            timeout: timeout.map(Duration::from_secs_f64),
+            memory: memory.map(|mib| mib * 1024 * 1024),
+        })
+    }
+
+    /// Parse the subcommands of `cargo crypt`.
+    fn parse_crypt(
+        args: &mut pico_args::Arguments,
+    ) -> Result<AppArguments, Box<dyn std::error::Error>> {
+        Ok(match args.subcommand()?.as_deref() {
+            Some("keygen") => AppArguments::Keygen,
+            Some("encrypt") => AppArguments::Encrypt {
+                day: args.opt_free_from_str()?,
+            },
+            Some("decrypt") => AppArguments::Decrypt {
+                day: args.opt_free_from_str()?,
+            },
+            _ => {
+                return Err(
+                    "expected `cargo crypt keygen`, `encrypt [day]` or `decrypt [day]`.".into(),
+                )
+            }
+        })
     }
 }
 
 fn main() {
-    match parse() {
-        Err(err) => {
-            eprintln!("Error: {err}");
-            std::process::exit(1);
+    let result = match parse() {
+        Err(err) => Err(CommandError::Parse(format!("Error: {err}"))),
+        Ok((year, args)) => run(year, args),
+    };
+
+    if let Err(err) = result {
+        eprintln!("{err}");
+        process::exit(err.exit_code());
+    }
+}
+
+fn run(year: Year, args: AppArguments) -> Result<(), CommandError> {
+    match args {
+        AppArguments::All {
+            release,
+            jobs,
+            limits,
+            format,
+        } => all::handle(year, solutions::SOLUTIONS, release, jobs, limits, format),
+        AppArguments::Time {
+            day,
+            all,
+            store,
+            compare,
+            heap,
+            limits,
+            format,
+        } => time::handle(
+            year,
+            solutions::SOLUTIONS,
+            day,
+            all,
+            store,
+            compare,
+            heap,
+            limits,
+            format,
+        ),
+        AppArguments::History { day } => time::history(year, day),
+        AppArguments::Report { path } => time::report(year, &path),
+        AppArguments::Verify { day, record } => {
+            verify::handle(year, solutions::SOLUTIONS, day, record)
         }
-        Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold {
-                day,
-                download,
-                overwrite,
-            } => {
-                scaffold::handle(day, overwrite);
-                if download {
-                    download::handle(day);
-                }
+        AppArguments::Inputs {
+            day,
+            dir,
+            release,
+            check,
+            format,
+        } => inputs::handle(
+            year,
+            solutions::SOLUTIONS,
+            day,
+            &dir,
+            release,
+            check,
+            format,
+        ),
+        AppArguments::Keygen => crypt::keygen(),
+        AppArguments::Encrypt { day } => crypt::encrypt(year, day),
+        AppArguments::Decrypt { day } => crypt::decrypt(year, day),
+        AppArguments::Download { day } => download::handle(year, day),
+        AppArguments::Read { day } => read::handle(year, day),
+        AppArguments::Examples { day, overwrite } => examples::handle(year, day, overwrite),
+        AppArguments::Scaffold {
+            day,
+            download,
+            overwrite,
+        } => {
+            scaffold::handle(year, day, overwrite)?;
+            if download {
+                download::handle(year, day)?;
             }
-            AppArguments::Solve {
-                day,
-                release,
-                dhat,
-                submit,
-            } => solve::handle(day, release, dhat, submit),
-            #[cfg(feature = "today")]
-            AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
-                    }
-                    None => {
-                        eprintln!(
-                            "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
-                        );
-                        process::exit(1)
-                    }
-                };
+            Ok(())
+        }
+        AppArguments::Solve {
+            day,
+            release,
+            dhat,
+            submit,
+            input,
+            watch,
+            format,
+        } => {
+            if watch && input.as_deref() == Some("-") {
+                // stdin is at its end after the first run, so it cannot be read on every change.
+                Err(CommandError::Parse(
+                    "`--watch` cannot read the input from stdin, pass a file with `--input`."
+                        .into(),
+                ))
+            } else if watch {
+                solve::watch(year, day, release, input.as_deref(), format);
+                Ok(())
+            } else {
+                solve::handle(year, day, release, dhat, submit, input.as_deref(), format)
             }
-        },
-    };
+        }
+        #[cfg(feature = "today")]
+        AppArguments::Today => {
+            let Some((day, year)) = Day::today().zip(Year::today()) else {
+                return Err(CommandError::Parse(
+                    "`today` command can only be run between the 1st and \
+                    the 25th of december. Please use `scaffold` with a specific day."
+                        .into(),
+                ));
+            };
+
+            scaffold::handle(year, day, false)?;
+            download::handle(year, day)?;
+            read::handle(year, day)
+        }
+    }
 }
@@ -0,0 +1,245 @@
+use std::{
+    collections::HashMap,
+    fs,
+    io::{Error, ErrorKind},
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::{paths, Day, Year};
+
+static ANSWERS_FILE_NAME: &str = "answers.json";
+
+/// Represents the known-correct answers for a single day.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Answer {
+    pub day: Day,
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+/// Represents the known-correct answers for a set of days.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Answers {
+    pub data: Vec<Answer>,
+}
+
+impl Answers {
+    /// Dehydrate answers to a JSON file.
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(paths::data_file(year, ANSWERS_FILE_NAME))?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate answers from a JSON file. If not present, returns empty answers.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the file could not be read or is not a valid answer store, so
+    /// that storing answers never overwrites a file it could not understand.
+    pub fn read_from_file(year: Year) -> Result<Self, Error> {
+        let path = paths::data_file(year, ANSWERS_FILE_NAME);
+        Self::from_contents(&path, fs::read_to_string(&path))
+    }
+
+    fn from_contents(path: &str, contents: Result<String, Error>) -> Result<Self, Error> {
+        match contents {
+            Ok(contents) => Self::try_from(contents)
+                .map_err(|e| Error::new(ErrorKind::InvalidData, format!("{path}: {e}"))),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Get the expected answer for a part, if known.
+    pub fn get(&self, day: Day, part: u8) -> Option<&str> {
+        let answer = self.data.iter().find(|a| a.day == day)?;
+        match part {
+            1 => answer.part_1.as_deref(),
+            2 => answer.part_2.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Set the expected answer for a part, keeping days sorted.
+    pub fn set(&mut self, day: Day, part: u8, value: &str) {
+        if !self.data.iter().any(|a| a.day == day) {
+            self.data.push(Answer {
+                day,
+                part_1: None,
+                part_2: None,
+            });
+            self.data.sort_unstable_by_key(|a| a.day);
+        }
+
+        let Some(answer) = self.data.iter_mut().find(|a| a.day == day) else {
+            return;
+        };
+
+        match part {
+            1 => answer.part_1 = Some(value.into()),
+            2 => answer.part_2 = Some(value.into()),
+            _ => {}
+        }
+    }
+}
+
+/// Remember an answer that was accepted by the server in the answer store.
+pub fn record_accepted(year: Year, day: Day, part: u8, value: &str) -> Result<(), Error> {
+    let mut answers = Answers::read_from_file(year)?;
+    answers.set(day, part, value);
+    answers.store_file(year)
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Answers> for JsonValue {
+    fn from(value: Answers) -> Self {
+        let mut map: HashMap<String, Self> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            Self::Array(value.data.iter().map(Self::from).collect()),
+        );
+
+        Self::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Self {
+            data: json_data
+                .iter()
+                .map(Answer::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Answer> for JsonValue {
+    fn from(value: &Answer) -> Self {
+        let mut map: HashMap<String, Self> = HashMap::new();
+
+        map.insert("day".into(), Self::String(value.day.to_string()));
+
+        let part_1 = value.part_1.clone().map(Self::String);
+        let part_2 = value.part_2.clone().map(Self::String);
+
+        map.insert("part_1".into(), part_1.map_or(Self::Null, |x| x));
+
+        map.insert("part_2".into(), part_2.map_or(Self::Null, |x| x));
+
+        Self::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Answer {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected answer to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected answer.day to be a Day struct.")?;
+
+        let part_1 = json
+            .get("part_1")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected answer.part_1 to be null or string.")?;
+
+        let part_2 = json
+            .get("part_2")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected answer.part_2 to be null or string.")?;
+
+        Ok(Self {
+            day,
+            part_1: part_1.cloned(),
+            part_2: part_2.cloned(),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::io::{Error, ErrorKind};
+
+    use super::Answers;
+    use crate::day;
+
+    #[test]
+    fn handles_json_answers() {
+        let json = r#"{ "data": [{ "day": "01", "part_1": "7", "part_2": null }] }"#.to_string();
+        let answers = Answers::try_from(json).unwrap();
+        assert_eq!(answers.get(day!(1), 1), Some("7"));
+        assert_eq!(answers.get(day!(1), 2), None);
+        assert_eq!(answers.get(day!(2), 1), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_answers() {
+        let json = r#"{ "data": [{ "day": "26", "part_1": "7" }] }"#.to_string();
+        Answers::try_from(json).unwrap();
+    }
+
+    #[test]
+    fn refuses_corrupt_answer_files() {
+        let missing = Err(Error::from(ErrorKind::NotFound));
+        let answers = Answers::from_contents("answers.json", missing).unwrap();
+        assert!(answers.data.is_empty());
+
+        let corrupt = Ok(r#"{ "data": [{ "day": "01""#.to_string());
+        let error = Answers::from_contents("answers.json", corrupt).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+        assert!(error.to_string().starts_with("answers.json: "));
+    }
+
+    #[test]
+    fn sets_answers_in_day_order() {
+        let mut answers = Answers::default();
+        answers.set(day!(3), 2, "multi\nline");
+        answers.set(day!(1), 1, "7");
+        answers.set(day!(3), 1, "42");
+        answers.set(day!(1), 1, "8");
+
+        assert_eq!(answers.data.len(), 2);
+        assert_eq!(answers.data[0].day, day!(1));
+        assert_eq!(answers.get(day!(1), 1), Some("8"));
+        assert_eq!(answers.get(day!(3), 1), Some("42"));
+        assert_eq!(answers.get(day!(3), 2), Some("multi\nline"));
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let mut answers = Answers::default();
+        answers.set(day!(12), 2, "abc");
+        let json = tinyjson::JsonValue::from(answers).stringify().unwrap();
+        let parsed = Answers::try_from(json).unwrap();
+        assert_eq!(parsed.get(day!(12), 2), Some("abc"));
+    }
+}
@@ -0,0 +1,604 @@
+/// Minimal client for the Advent of Code website.
+///
+/// Fetches puzzle inputs and descriptions and submits answers over HTTP, authenticating with the
+/// session cookie of a logged-in browser. The cookie is read from the `AOC_SESSION` env var or
+/// from an `adventofcode.session` file (the same locations aoc-cli used).
+use std::{
+    env,
+    fmt::{Display, Write},
+    fs, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use crate::template::crypt::{self, InputKey};
+use crate::template::{paths, Day, Year};
+
+static DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+static SESSION_FILE_NAME: &str = "adventofcode.session";
+static USER_AGENT: &str = concat!(
+    env!("CARGO_PKG_NAME"),
+    "/",
+    env!("CARGO_PKG_VERSION"),
+    " (github.com/fspoettel/advent-of-code-rust)"
+);
+
+#[derive(Debug)]
+pub enum AocClientError {
+    SessionNotFound,
+    BadStatus(u16, String),
+    Transport(String),
+    IO(io::Error),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::SessionNotFound => write!(
+                f,
+                "no session cookie found. Set `AOC_SESSION` or write it to `~/.{SESSION_FILE_NAME}`."
+            ),
+            Self::BadStatus(status, body) => {
+                write!(f, "server responded with status {status}: {}", body.trim())
+            }
+            Self::Transport(e) => write!(f, "request failed: {e}"),
+            Self::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<io::Error> for AocClientError {
+    fn from(e: io::Error) -> Self {
+        Self::IO(e)
+    }
+}
+
+impl From<ureq::Error> for AocClientError {
+    fn from(e: ureq::Error) -> Self {
+        match e {
+            ureq::Error::Status(status, response) => {
+                Self::BadStatus(status, response.into_string().unwrap_or_default())
+            }
+            ureq::Error::Transport(e) => Self::Transport(e.to_string()),
+        }
+    }
+}
+
+/// The server's verdict on a submitted answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Incorrect,
+    /// An answer was submitted too recently; holds the remaining seconds, if stated.
+    Wait(Option<u64>),
+    /// The part was already solved or is not unlocked yet.
+    WrongLevel,
+    Unknown,
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Correct => f.write_str("correct"),
+            Self::TooHigh => f.write_str("too high"),
+            Self::TooLow => f.write_str("too low"),
+            Self::Incorrect => f.write_str("incorrect"),
+            Self::Wait(_) => f.write_str("wait"),
+            Self::WrongLevel => f.write_str("wrong level"),
+            Self::Unknown => f.write_str("unknown"),
+        }
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    /// Parses the [`Display`] representation. A `wait` verdict does not carry its duration.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Self::Correct),
+            "too high" => Ok(Self::TooHigh),
+            "too low" => Ok(Self::TooLow),
+            "incorrect" => Ok(Self::Incorrect),
+            "wait" => Ok(Self::Wait(None)),
+            "wrong level" => Ok(Self::WrongLevel),
+            "unknown" => Ok(Self::Unknown),
+            _ => Err(format!("unknown verdict `{s}`.")),
+        }
+    }
+}
+
+/// The response to a submitted answer.
+#[derive(Clone, Debug)]
+pub struct Submission {
+    pub verdict: Verdict,
+    /// The server's message, converted to plain text.
+    pub message: String,
+    /// Seconds until the server accepts the next answer, if it asked to wait. Wrong answers
+    /// lock out further submissions for a while.
+    pub wait_seconds: Option<u64>,
+}
+
+pub struct AocClient {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl AocClient {
+    #[must_use]
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+            agent: ureq::AgentBuilder::new().user_agent(USER_AGENT).build(),
+        }
+    }
+
+    /// Create a client from the environment. The base URL can be overridden with `AOC_BASE_URL`.
+    ///
+    /// # Errors
+    ///
+    /// Will return `AocClientError` if no session cookie is configured.
+    pub fn from_env() -> Result<Self, AocClientError> {
+        let session = read_session().ok_or(AocClientError::SessionNotFound)?;
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        Ok(Self::new(&base_url, &session))
+    }
+
+    /// Fetch the puzzle input for a day.
+    ///
+    /// # Errors
+    ///
+    /// Will return `AocClientError` if the request fails or the server responds with an error.
+    pub fn input(&self, year: Year, day: Day) -> Result<String, AocClientError> {
+        let url = format!("{}/input", self.day_url(year, day));
+        let response = self.agent.get(&url).set("Cookie", &self.cookie()).call()?;
+        Ok(response.into_string()?)
+    }
+
+    /// Fetch the puzzle description for a day, converted to markdown.
+    ///
+    /// # Errors
+    ///
+    /// Will return `AocClientError` if the request fails or the server responds with an error.
+    pub fn puzzle(&self, year: Year, day: Day) -> Result<String, AocClientError> {
+        let url = self.day_url(year, day);
+        let response = self.agent.get(&url).set("Cookie", &self.cookie()).call()?;
+        Ok(html_to_markdown(&response.into_string()?))
+    }
+
+    /// Submit an answer for one part of a day.
+    ///
+    /// # Errors
+    ///
+    /// Will return `AocClientError` if the request fails or the server responds with an error.
+    pub fn submit(
+        &self,
+        year: Year,
+        day: Day,
+        part: u8,
+        answer: &str,
+    ) -> Result<Submission, AocClientError> {
+        let url = format!("{}/answer", self.day_url(year, day));
+        let response = self
+            .agent
+            .post(&url)
+            .set("Cookie", &self.cookie())
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])?;
+
+        Ok(parse_submission(&response.into_string()?))
+    }
+
+    fn day_url(&self, year: Year, day: Day) -> String {
+        format!("{}/{year}/day/{}", self.base_url, day.into_inner())
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// # Errors
+///
+/// Will return `AocClientError` if the client is not configured, the request fails or the
+/// puzzle file could not be written.
+pub fn read(year: Year, day: Day) -> Result<(), AocClientError> {
+    let client = AocClient::from_env()?;
+    let puzzle = client.puzzle(year, day)?;
+    write_file(paths::puzzle_file(year, day), &puzzle)?;
+    println!("{puzzle}");
+    Ok(())
+}
+
+/// # Errors
+///
+/// Will return `AocClientError` if the client is not configured, a request fails or the
+/// files could not be written.
+pub fn download(year: Year, day: Day) -> Result<(), AocClientError> {
+    let client = AocClient::from_env()?;
+    let input_path = paths::day_file("inputs", year, day);
+    let puzzle_path = paths::puzzle_file(year, day);
+
+    let input = client.input(year, day)?;
+
+    // keep inputs encrypted at rest if the project is set up with an input key.
+    let input_path = match InputKey::from_env() {
+        Ok(Some(key)) => {
+            let path = crypt::encrypted_path(Path::new(&input_path));
+            write_file(&path, key.encrypt(input.as_bytes()))?;
+            // a plain input, be it the placeholder of `scaffold` or an older download, would
+            // shadow the encrypted input.
+            if Path::new(&input_path).exists() {
+                fs::remove_file(&input_path)?;
+                println!("Removed the plain input \"{input_path}\" in favor of the encrypted one.");
+            }
+            path.to_string_lossy().into_owned()
+        }
+        Ok(None) => {
+            write_file(&input_path, input)?;
+            input_path
+        }
+        Err(e) => return Err(AocClientError::IO(io::Error::other(e))),
+    };
+
+    write_file(&puzzle_path, client.puzzle(year, day)?)?;
+
+    println!("---");
+    println!("🎄 Successfully wrote input to \"{}\".", &input_path);
+    println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+    Ok(())
+}
+
+/// Submit an answer. The server's response is returned as the submission's message.
+///
+/// # Errors
+///
+/// Will return `AocClientError` if the client is not configured or the request fails.
+pub fn submit(year: Year, day: Day, part: u8, answer: &str) -> Result<Submission, AocClientError> {
+    let client = AocClient::from_env()?;
+    client.submit(year, day, part, answer)
+}
+
+/// Write a file, creating the data folders of a year that was not used before.
+fn write_file(path: impl AsRef<Path>, contents: impl AsRef<[u8]>) -> io::Result<()> {
+    let path = path.as_ref();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, contents)
+}
+
+/// Read the session cookie from `AOC_SESSION`, `~/.adventofcode.session` or
+/// `$XDG_CONFIG_HOME/adventofcode.session`, in that order.
+fn read_session() -> Option<String> {
+    if let Ok(session) = env::var("AOC_SESSION") {
+        return Some(session.trim().to_string());
+    }
+
+    let home = env::var_os("HOME").map(PathBuf::from);
+    let config_dir = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| home.as_ref().map(|h| h.join(".config")));
+
+    [
+        home.map(|h| h.join(format!(".{SESSION_FILE_NAME}"))),
+        config_dir.map(|c| c.join(SESSION_FILE_NAME)),
+    ]
+    .into_iter()
+    .flatten()
+    .find_map(|path| fs::read_to_string(path).ok())
+    .map(|session| session.trim().to_string())
+    .filter(|session| !session.is_empty())
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Classify the `<article>` of an answer response.
+fn parse_submission(html: &str) -> Submission {
+    let message = html_to_markdown(html);
+
+    let verdict = if message.contains("That's the right answer") {
+        Verdict::Correct
+    } else if message.contains("too high") {
+        Verdict::TooHigh
+    } else if message.contains("too low") {
+        Verdict::TooLow
+    } else if message.contains("That's not the right answer") {
+        Verdict::Incorrect
+    } else if message.contains("You gave an answer too recently") {
+        Verdict::Wait(parse_wait_seconds(&message))
+    } else if message.contains("You don't seem to be solving the right level") {
+        Verdict::WrongLevel
+    } else {
+        Verdict::Unknown
+    };
+
+    let wait_seconds = match verdict {
+        Verdict::Wait(seconds) => seconds,
+        _ => parse_lockout_seconds(&message),
+    };
+
+    Submission {
+        verdict,
+        message,
+        wait_seconds,
+    }
+}
+
+/// Parse "You have 1m 5s left to wait." into seconds.
+fn parse_wait_seconds(message: &str) -> Option<u64> {
+    let end = message.find(" left to wait")?;
+    let start = message[..end].rfind("You have ")? + "You have ".len();
+
+    message[start..end]
+        .split_whitespace()
+        .map(|token| {
+            let (value, multiplier) = match token.strip_suffix('m') {
+                Some(minutes) => (minutes, 60),
+                None => (token.strip_suffix('s')?, 1),
+            };
+            value.parse::<u64>().ok().map(|v| v * multiplier)
+        })
+        .sum()
+}
+
+/// Parse the lockout after a wrong answer, e.g. "please wait 5 minutes before trying again.",
+/// into seconds. Small amounts are spelled out, as in "Please wait one minute".
+fn parse_lockout_seconds(message: &str) -> Option<u64> {
+    let message = message.to_lowercase();
+    let end = message.find(" before trying again")?;
+    let start = message[..end].rfind("wait ")? + "wait ".len();
+
+    let (amount, unit) = message[start..end].split_once(' ')?;
+    let amount = match amount {
+        "one" => 1,
+        "two" => 2,
+        "three" => 3,
+        "four" => 4,
+        "five" => 5,
+        "ten" => 10,
+        amount => amount.parse().ok()?,
+    };
+    let multiplier = match unit.trim_end_matches('s') {
+        "second" => 1,
+        "minute" => 60,
+        "hour" => 3600,
+        _ => return None,
+    };
+
+    Some(amount * multiplier)
+}
+
+/// Convert the `<article>` elements of a page to markdown.
+/// Only handles the handful of tags used in puzzle descriptions and answer responses.
+fn html_to_markdown(html: &str) -> String {
+    let mut out = String::new();
+    let mut rest = html;
+
+    while let Some(start) = rest.find("<article") {
+        let Some(end) = rest[start..].find("</article>") else {
+            break;
+        };
+        let article = &rest[start..start + end];
+        // skip the opening tag itself.
+        let body = article.find('>').map_or("", |i| &article[i + 1..]);
+        convert_fragment(body, &mut out);
+        rest = &rest[start + end + "</article>".len()..];
+    }
+
+    let trimmed = out.trim();
+    if trimmed.is_empty() {
+        String::new()
+    } else {
+        format!("{trimmed}\n")
+    }
+}
+
+fn convert_fragment(html: &str, out: &mut String) {
+    let mut in_pre = false;
+    let mut links: Vec<String> = Vec::new();
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        let open = rest.find('<').unwrap_or(rest.len());
+        let text = &rest[..open];
+
+        // whitespace between block elements is layout only.
+        if in_pre || !text.trim().is_empty() {
+            out.push_str(&decode_entities(text));
+        }
+
+        let Some(close) = rest[open..].find('>') else {
+            break;
+        };
+
+        let tag = &rest[open + 1..open + close];
+        rest = &rest[open + close + 1..];
+
+        let is_closing = tag.starts_with('/');
+        let name = tag
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default();
+
+        match (name, is_closing) {
+            ("h2", false) => out.push_str("## "),
+            ("h2" | "p" | "ul", true) => out.push_str("\n\n"),
+            ("pre", false) => {
+                in_pre = true;
+                out.push_str("```\n");
+            }
+            ("pre", true) => {
+                in_pre = false;
+                if !out.ends_with('\n') {
+                    out.push('\n');
+                }
+                out.push_str("```\n\n");
+            }
+            ("code", _) if !in_pre => out.push('`'),
+            ("em", _) if !in_pre => out.push('*'),
+            ("li", false) => out.push_str("- "),
+            ("li", true) => out.push('\n'),
+            ("a", false) => {
+                links.push(attribute(tag, "href").unwrap_or_default().to_string());
+                out.push('[');
+            }
+            ("a", true) => {
+                let href = links.pop().unwrap_or_default();
+                let _ = write!(out, "]({href})");
+            }
+            _ => {}
+        }
+    }
+}
+
+fn attribute<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let needle = format!("{name}=\"");
+    let start = tag.find(&needle)? + needle.len();
+    let end = tag[start..].find('"')?;
+    Some(&tag[start..start + end])
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+        thread,
+    };
+
+    use super::{
+        html_to_markdown, parse_lockout_seconds, parse_submission, parse_wait_seconds, AocClient,
+        Verdict,
+    };
+    use crate::{day, year};
+
+    #[test]
+    fn converts_puzzle_html() {
+        let html = r#"<main><article class="day-desc"><h2>--- Day 1: Sonar Sweep ---</h2>
+<p>For example, see <a href="/2021/about">this</a> &amp; <em>that</em>:</p>
+<pre><code>199
+<em>200</em>
+</code></pre>
+<ul><li>It uses <code>a &lt; b</code>.</li></ul></article></main>"#;
+
+        assert_eq!(
+            html_to_markdown(html),
+            "## --- Day 1: Sonar Sweep ---\n\nFor example, see [this](/2021/about) & *that*:\n\n```\n199\n200\n```\n\n- It uses `a < b`.\n"
+        );
+    }
+
+    #[test]
+    fn parses_verdicts() {
+        let wrap = |s: &str| format!("<main><article><p>{s}</p></article></main>");
+        let verdict = |s: &str| parse_submission(&wrap(s)).verdict;
+
+        assert_eq!(
+            verdict("That's the right answer! You are one gold star closer."),
+            Verdict::Correct
+        );
+        assert_eq!(
+            verdict("That's not the right answer; your answer is too high."),
+            Verdict::TooHigh
+        );
+        assert_eq!(
+            verdict("That's not the right answer; your answer is too low."),
+            Verdict::TooLow
+        );
+        assert_eq!(
+            verdict("That's not the right answer. If you're stuck, ..."),
+            Verdict::Incorrect
+        );
+        assert_eq!(
+            verdict("You gave an answer too recently. You have 1m 5s left to wait."),
+            Verdict::Wait(Some(65))
+        );
+        assert_eq!(
+            verdict("You don't seem to be solving the right level."),
+            Verdict::WrongLevel
+        );
+    }
+
+    #[test]
+    fn parses_wait_times() {
+        assert_eq!(parse_wait_seconds("You have 37s left to wait."), Some(37));
+        assert_eq!(
+            parse_wait_seconds("You have 2m 0s left to wait."),
+            Some(120)
+        );
+        assert_eq!(parse_wait_seconds("Please wait."), None);
+    }
+
+    #[test]
+    fn parses_lockouts_after_wrong_answers() {
+        assert_eq!(
+            parse_lockout_seconds("Please wait one minute before trying again."),
+            Some(60)
+        );
+        assert_eq!(
+            parse_lockout_seconds(
+                "Because you have guessed incorrectly 4 times on this puzzle, please wait 5 minutes before trying again."
+            ),
+            Some(300)
+        );
+        assert_eq!(parse_lockout_seconds("Please try again."), None);
+
+        let submission = parse_submission(
+            "<main><article><p>That's not the right answer. Please wait one minute before trying again.</p></article></main>",
+        );
+        assert_eq!(submission.verdict, Verdict::Incorrect);
+        assert_eq!(submission.wait_seconds, Some(60));
+    }
+
+    #[test]
+    fn fetches_from_configured_base_url() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+            let mut request = String::new();
+            let mut line = String::new();
+            while reader.read_line(&mut line).unwrap() > 2 {
+                request.push_str(&line);
+                line.clear();
+            }
+
+            let body = "1\n2\n3\n";
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+            request
+        });
+
+        let client = AocClient::new(&format!("http://{address}/"), "abc");
+        assert_eq!(client.input(year!(2021), day!(7)).unwrap(), "1\n2\n3\n");
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("GET /2021/day/7/input HTTP/1.1"));
+        assert!(request.contains("session=abc"));
+    }
+}
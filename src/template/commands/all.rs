@@ -1,5 +1,48 @@
-use crate::template::{all_days, run_multi::run_multi};
+use crate::template::{
+    all_days, commands::CommandError, registry::Solution, run_multi::run_multi, timings::Timings,
+    Limits, OutputFormat, Year,
+};
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+/// Run every day of a year.
+///
+/// # Errors
+///
+/// Will return an error if a day could not be run, or if a part failed or a day was aborted.
+pub fn handle(
+    year: Year,
+    solutions: &[Solution],
+    is_release: bool,
+    jobs: usize,
+    limits: Limits,
+    format: OutputFormat,
+) -> Result<(), CommandError> {
+    let timings = run_multi(
+        year,
+        &all_days().collect(),
+        solutions,
+        is_release,
+        false,
+        false,
+        jobs,
+        limits,
+        format,
+    )?;
+
+    check_failed_days(&timings)
+}
+
+/// Fail with a solution error if any day of `timings` failed or was aborted.
+pub(crate) fn check_failed_days(timings: &Timings) -> Result<(), CommandError> {
+    let failed = timings.failed_days();
+
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        let days: Vec<String> = failed.iter().map(|day| format!("Day {day}")).collect();
+        Err(CommandError::Solution(format!(
+            "{} day(s) failed or were aborted: {}.",
+            failed.len(),
+            days.join(", ")
+        )))
+    }
 }
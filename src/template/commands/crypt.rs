@@ -0,0 +1,102 @@
+use std::path::PathBuf;
+use std::{fs, io};
+
+use crate::template::commands::CommandError;
+use crate::template::crypt::{self, InputKey, KEY_FILE_NAME};
+use crate::template::{all_days, paths, Day, Year};
+
+/// Create a new input key in the project root, unless one exists already.
+///
+/// # Errors
+///
+/// Will return an error if a key exists already or could not be written.
+pub fn keygen() -> Result<(), CommandError> {
+    let path = paths::resolve(KEY_FILE_NAME);
+
+    if path.exists() {
+        return Err(CommandError::IO(
+            format!("An input key already exists at `{KEY_FILE_NAME}`"),
+            io::Error::from(io::ErrorKind::AlreadyExists),
+        ));
+    }
+
+    fs::write(&path, format!("{}\n", InputKey::generate()))
+        .map_err(CommandError::io("Failed to write input key"))?;
+
+    println!("Created input key at `{KEY_FILE_NAME}`. Share it privately, never commit it.");
+    Ok(())
+}
+
+/// Replace the plain input files of a year, or of a single day, with encrypted blobs.
+///
+/// # Errors
+///
+/// Will return an error if there is no input key or an input could not be encrypted.
+pub fn encrypt(year: Year, day: Option<Day>) -> Result<(), CommandError> {
+    let key = read_key()?;
+    let mut count = 0;
+
+    for path in day_files(year, day) {
+        // skip the empty placeholders created by `scaffold`.
+        let Ok(input) = fs::read(&path) else {
+            continue;
+        };
+        if input.is_empty() {
+            continue;
+        }
+
+        fs::write(crypt::encrypted_path(&path), key.encrypt(&input))
+            .and_then(|()| fs::remove_file(&path))
+            .map_err(CommandError::io(format!(
+                "Failed to encrypt `{}`",
+                path.display()
+            )))?;
+
+        count += 1;
+    }
+
+    println!("Encrypted {count} input(s).");
+    Ok(())
+}
+
+/// Write plain input files for the encrypted blobs of a year, or of a single day.
+///
+/// # Errors
+///
+/// Will return an error if there is no valid input key or an input could not be decrypted.
+pub fn decrypt(year: Year, day: Option<Day>) -> Result<(), CommandError> {
+    let key = read_key()?;
+    let mut count = 0;
+
+    for path in day_files(year, day) {
+        let Ok(blob) = fs::read(crypt::encrypted_path(&path)) else {
+            continue;
+        };
+
+        key.decrypt(&blob)
+            .and_then(|input| fs::write(&path, input).map_err(Into::into))
+            .map_err(CommandError::crypt(format!(
+                "Failed to decrypt `{}`",
+                path.display()
+            )))?;
+
+        count += 1;
+    }
+
+    println!("Decrypted {count} input(s).");
+    Ok(())
+}
+
+fn read_key() -> Result<InputKey, CommandError> {
+    InputKey::from_env()
+        .map_err(CommandError::crypt("Failed to read input key"))?
+        .ok_or_else(|| CommandError::MissingInput(crypt::CryptError::KeyNotFound.to_string()))
+}
+
+/// Plain input paths of a year, or of a single day.
+fn day_files(year: Year, day: Option<Day>) -> Vec<PathBuf> {
+    let days: Vec<Day> = day.map_or_else(|| all_days().collect(), |day| vec![day]);
+    days.into_iter()
+        .map(|day| PathBuf::from(paths::day_file("inputs", year, day)))
+        .collect()
+}
@@ -1,14 +1,8 @@
-use crate::template::{aoc_cli, Day};
-use std::process;
+use crate::template::{aoc_client, commands::CommandError, Day, Year};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
-    }
-
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
-    }
+/// # Errors
+///
+/// Will return an error if the puzzle could not be downloaded or written.
+pub fn handle(year: Year, day: Day) -> Result<(), CommandError> {
+    aoc_client::download(year, day).map_err(CommandError::aoc_client("failed to download puzzle"))
 }
@@ -0,0 +1,150 @@
+/// The error returned by command handlers, with an exit code per class of failure.
+use std::{fmt::Display, io};
+
+use crate::template::{
+    aoc_client::AocClientError, crypt::CryptError, readme_benchmarks, run_multi,
+};
+
+#[derive(Debug)]
+pub enum CommandError {
+    /// A solution failed, panicked, was aborted or did not produce the expected answer.
+    Solution(String),
+    /// The command line or a data file could not be parsed.
+    Parse(String),
+    /// A file needed by the command does not exist, e.g. an input that was not downloaded.
+    MissingInput(String),
+    /// A request to the Advent of Code website failed.
+    AocClient(String, AocClientError),
+    /// Reading or writing a file, or running a process, failed.
+    IO(String, io::Error),
+}
+
+impl CommandError {
+    /// The exit code of the process when a command fails with this error.
+    ///
+    /// Solution failures keep the exit code `1` that `verify` and `time --compare` used before.
+    #[must_use]
+    pub const fn exit_code(&self) -> i32 {
+        match self {
+            Self::Solution(_) => 1,
+            Self::Parse(_) => 2,
+            Self::MissingInput(_) => 3,
+            Self::AocClient(..) => 4,
+            Self::IO(..) => 5,
+        }
+    }
+
+    /// Wrap an IO error with a description of what failed, for use with `map_err`.
+    pub fn io(context: impl Into<String>) -> impl FnOnce(io::Error) -> Self {
+        |e| Self::IO(context.into(), e)
+    }
+
+    /// Wrap an error of the Advent of Code client. Errors writing the fetched files are IO errors.
+    pub fn aoc_client(context: impl Into<String>) -> impl FnOnce(AocClientError) -> Self {
+        |e| match e {
+            AocClientError::IO(e) => Self::IO(context.into(), e),
+            e => Self::AocClient(context.into(), e),
+        }
+    }
+
+    /// Wrap an error of the input encryption. A missing key is reported as a missing input.
+    pub fn crypt(context: impl Into<String>) -> impl FnOnce(CryptError) -> Self {
+        |e| match e {
+            CryptError::KeyNotFound => Self::MissingInput(e.to_string()),
+            CryptError::IO(e) => Self::IO(context.into(), e),
+            e => Self::Parse(format!("{}: {e}", context.into())),
+        }
+    }
+}
+
+impl Display for CommandError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Solution(message) | Self::Parse(message) | Self::MissingInput(message) => {
+                f.write_str(message)
+            }
+            Self::AocClient(context, e) => write!(f, "{context}: {e}"),
+            Self::IO(context, e) => write!(f, "{context}: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for CommandError {}
+
+impl From<run_multi::Error> for CommandError {
+    fn from(e: run_multi::Error) -> Self {
+        let e = match e {
+            run_multi::Error::BrokenPipe => io::Error::from(io::ErrorKind::BrokenPipe),
+            run_multi::Error::IO(e) => e,
+        };
+        Self::IO("Failed to run solution".into(), e)
+    }
+}
+
+impl From<readme_benchmarks::Error> for CommandError {
+    fn from(e: readme_benchmarks::Error) -> Self {
+        let context = "Failed to store updated benchmarks";
+        match e {
+            readme_benchmarks::Error::Parser(message) => {
+                Self::Parse(format!("{context}: {message}"))
+            }
+            readme_benchmarks::Error::IO(e) => Self::IO(context.into(), e),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::io;
+
+    use super::CommandError;
+    use crate::template::{aoc_client::AocClientError, crypt::CryptError, readme_benchmarks};
+
+    #[test]
+    fn maps_variants_to_exit_codes() {
+        assert_eq!(CommandError::Solution(String::new()).exit_code(), 1);
+        assert_eq!(CommandError::Parse(String::new()).exit_code(), 2);
+        assert_eq!(CommandError::MissingInput(String::new()).exit_code(), 3);
+        assert_eq!(
+            CommandError::AocClient(String::new(), AocClientError::SessionNotFound).exit_code(),
+            4
+        );
+        assert_eq!(
+            CommandError::IO(String::new(), io::Error::other("")).exit_code(),
+            5
+        );
+    }
+
+    #[test]
+    fn classifies_wrapped_errors() {
+        let e = CommandError::aoc_client("failed to download puzzle")(AocClientError::IO(
+            io::Error::other("disk full"),
+        ));
+        assert_eq!(e.exit_code(), 5);
+        assert_eq!(e.to_string(), "failed to download puzzle: disk full");
+
+        let e = CommandError::aoc_client("failed to download puzzle")(AocClientError::BadStatus(
+            404,
+            "Not Found\n".into(),
+        ));
+        assert_eq!(e.exit_code(), 4);
+
+        assert_eq!(
+            CommandError::crypt("Failed to read input key")(CryptError::KeyNotFound).exit_code(),
+            3
+        );
+        assert_eq!(
+            CommandError::crypt("Failed to read input key")(CryptError::InvalidKey).exit_code(),
+            2
+        );
+
+        let e = CommandError::from(readme_benchmarks::Error::Parser("no marker.".into()));
+        assert_eq!(e.exit_code(), 2);
+        assert_eq!(
+            e.to_string(),
+            "Failed to store updated benchmarks: no marker."
+        );
+    }
+}
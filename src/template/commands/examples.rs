@@ -0,0 +1,145 @@
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+
+use crate::template::commands::CommandError;
+use crate::template::examples::{code_blocks, empty_header, likely_example, CodeBlock};
+use crate::template::{paths, Day, Year, ANSI_BOLD, ANSI_RESET};
+
+/// Extract the example input of a day from its downloaded puzzle description.
+///
+/// Lists the code blocks of the puzzle and asks which one to use per part, suggesting the most
+/// likely example. If both parts use different examples, `NN-1.txt` and `NN-2.txt` are written
+/// alongside `NN.txt` for use with `read_file_part`. Every file starts with a header that
+/// declares its parts without answers, to be filled in with the expected answers.
+///
+/// # Errors
+///
+/// Will return an error if the puzzle was not downloaded or has no code blocks, or if an
+/// example could not be written.
+pub fn handle(year: Year, day: Day, overwrite: bool) -> Result<(), CommandError> {
+    let puzzle_path = paths::puzzle_file(year, day);
+
+    let Ok(puzzle) = fs::read_to_string(&puzzle_path) else {
+        return Err(CommandError::MissingInput(format!(
+            "Could not read \"{puzzle_path}\". Run `cargo download {day}` first."
+        )));
+    };
+
+    let blocks = code_blocks(&puzzle);
+
+    let Some(suggested) = likely_example(&blocks, 1) else {
+        return Err(CommandError::Parse(
+            "The puzzle description does not contain any code blocks.".into(),
+        ));
+    };
+
+    println!("Found {} code block(s) in \"{puzzle_path}\":", blocks.len());
+    for (i, block) in blocks.iter().enumerate() {
+        print_block(i, block);
+    }
+    println!();
+
+    let part_one = prompt(1, suggested, blocks.len())?;
+
+    let part_two = if blocks.iter().any(|block| block.part == 2) {
+        prompt(
+            2,
+            likely_example(&blocks, 2).unwrap_or(part_one),
+            blocks.len(),
+        )?
+    } else {
+        part_one
+    };
+
+    let example_one = &blocks[part_one].content;
+    let example_two = &blocks[part_two].content;
+
+    let day_file = paths::day_file("examples", year, day);
+
+    if example_one == example_two {
+        write_example(&day_file, &[1, 2], example_one, overwrite)?;
+    } else {
+        let part_file = |part| paths::day_part_file("examples", year, day, part);
+        write_example(&day_file, &[1], example_one, overwrite)?;
+        write_example(&part_file(1), &[1], example_one, overwrite)?;
+        write_example(&part_file(2), &[2], example_two, overwrite)?;
+    }
+
+    Ok(())
+}
+
+fn print_block(index: usize, block: &CodeBlock) {
+    let first_line = block.content.lines().next().unwrap_or_default();
+    let preview: String = first_line.chars().take(40).collect();
+    let ellipsis = if first_line.chars().count() > preview.chars().count() {
+        "…"
+    } else {
+        ""
+    };
+
+    println!(
+        "  [{index}] part {}, {} line(s){}: {preview}{ellipsis}",
+        block.part,
+        block.line_count(),
+        if block.follows_example {
+            " (example)"
+        } else {
+            ""
+        },
+    );
+}
+
+/// Ask for the block to use as the example of a part, accepting the suggestion on empty input.
+fn prompt(part: u8, suggested: usize, count: usize) -> Result<usize, CommandError> {
+    let stdin = io::stdin();
+
+    loop {
+        print!("{ANSI_BOLD}Example for part {part}{ANSI_RESET} [{suggested}]: ");
+        let _ = io::stdout().flush();
+
+        let mut line = String::new();
+        let read = stdin.lock().read_line(&mut line);
+        match read {
+            // accept the suggestion when stdin is closed, e.g. in scripts.
+            Ok(0) => {
+                println!();
+                return Ok(suggested);
+            }
+            Ok(_) if line.trim().is_empty() => return Ok(suggested),
+            Ok(_) => match line.trim().parse::<usize>() {
+                Ok(index) if index < count => return Ok(index),
+                _ => eprintln!("Expected a block index between 0 and {}.", count - 1),
+            },
+            Err(e) => return Err(CommandError::IO("Failed to read selection".into(), e)),
+        }
+    }
+}
+
+fn write_example(
+    path: &str,
+    parts: &[u8],
+    example: &str,
+    overwrite: bool,
+) -> Result<(), CommandError> {
+    // scaffolded example files are empty and can be filled without `--overwrite`.
+    let has_content = fs::metadata(path).is_ok_and(|m| m.len() > 0);
+    if has_content && !overwrite {
+        return Err(CommandError::IO(
+            format!("\"{path}\" already has content"),
+            io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                "pass `--overwrite` to replace it.",
+            ),
+        ));
+    }
+
+    Path::new(path)
+        .parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|()| fs::write(path, format!("{}{example}", empty_header(parts))))
+        .map_err(CommandError::io("Failed to write example"))?;
+
+    println!("Wrote example to \"{path}\"");
+    Ok(())
+}
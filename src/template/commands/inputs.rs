@@ -0,0 +1,267 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use tinyjson::JsonValue;
+
+use crate::template::commands::CommandError;
+use crate::template::record::{part_label, PartRecord, PartStatus};
+use crate::template::registry::{self, Solution};
+use crate::template::run_multi::{run_day_on, DayOutput};
+use crate::template::{Day, OutputFormat, Year, ANSI_BOLD, ANSI_RESET};
+
+/// The answers of a day on a single input file.
+struct InputRun {
+    name: String,
+    records: Vec<PartRecord>,
+}
+
+impl InputRun {
+    /// An input is flagged if one of `parts` did not produce an answer, including parts that
+    /// never ran.
+    fn is_flagged(&self, parts: &[u8]) -> bool {
+        parts.iter().any(|&part| {
+            self.record(part)
+                .is_none_or(|record| record.status != PartStatus::Solved)
+        })
+    }
+
+    fn record(&self, part: u8) -> Option<&PartRecord> {
+        self.records.iter().find(|record| record.part == part)
+    }
+}
+
+/// Run the parts of a day on every file in `dir` and print a table of answers per file.
+/// Encrypted blobs in the directory are skipped.
+///
+/// # Errors
+///
+/// Will return an error if `dir` holds no input files or a day could not be run. With `check`,
+/// also if any input is flagged, i.e. a part returned no answer, failed or panicked.
+pub fn handle(
+    year: Year,
+    solutions: &[Solution],
+    day: Day,
+    dir: &str,
+    release: bool,
+    check: bool,
+    format: OutputFormat,
+) -> Result<(), CommandError> {
+    let files = input_files(Path::new(dir)).map_err(CommandError::io(format!(
+        "Could not read input directory `{dir}`"
+    )))?;
+
+    if files.is_empty() {
+        return Err(CommandError::MissingInput(format!(
+            "No input files found in `{dir}`."
+        )));
+    }
+
+    let runs: Vec<InputRun> = files
+        .iter()
+        .map(|file| {
+            let records = run_day_on(
+                year,
+                day,
+                Some(file),
+                solutions,
+                release,
+                false,
+                &|output| {
+                    if let DayOutput::Stderr(line) = output {
+                        eprintln!("{line}");
+                    }
+                },
+            )?;
+
+            Ok(InputRun {
+                name: file
+                    .file_name()
+                    .map_or_else(String::new, |name| name.to_string_lossy().into()),
+                records,
+            })
+        })
+        .collect::<Result<_, CommandError>>()?;
+
+    let parts = expected_parts(registry::find(solutions, year, day), &runs);
+
+    match format {
+        OutputFormat::Json => print_json(&runs),
+        OutputFormat::Text => print_table(day, &runs, &parts),
+    }
+
+    let flagged = runs.iter().filter(|run| run.is_flagged(&parts)).count();
+
+    if flagged > 0 {
+        let message = format!("{flagged} input(s) did not produce an answer for every part.");
+
+        if check {
+            return Err(CommandError::Solution(message));
+        } else if !format.is_json() {
+            eprintln!("{message}");
+        }
+    }
+
+    Ok(())
+}
+
+/// Files in `dir`, sorted by name.
+fn input_files(dir: &Path) -> std::io::Result<Vec<PathBuf>> {
+    let mut files = vec![];
+
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_file() && path.extension().is_none_or(|ext| ext != "enc") {
+            files.push(path);
+        }
+    }
+
+    files.sort();
+    Ok(files)
+}
+
+/// Parts every input should produce an answer for: the parts the solution registers, or the
+/// parts that ran on any input if the day was run as a separate binary.
+fn expected_parts(solution: Option<&Solution>, runs: &[InputRun]) -> Vec<u8> {
+    let mut parts: Vec<u8> = solution.map_or_else(
+        || {
+            runs.iter()
+                .flat_map(|run| run.records.iter().map(|record| record.part))
+                .collect()
+        },
+        |solution| solution.parts.iter().map(|part| part.part).collect(),
+    );
+
+    parts.sort_unstable();
+    parts.dedup();
+    parts
+}
+
+fn print_table(day: Day, runs: &[InputRun], parts: &[u8]) {
+    let mut rows: Vec<Vec<String>> = vec![];
+
+    let mut header = vec!["Input".to_string()];
+    header.extend(parts.iter().map(|&part| part_label(part)));
+    rows.push(header);
+
+    for run in runs {
+        let mut row = vec![if run.is_flagged(parts) {
+            format!("{} ✖", run.name)
+        } else {
+            run.name.clone()
+        }];
+        row.extend(parts.iter().map(|&part| cell(run.record(part))));
+        rows.push(row);
+    }
+
+    let widths: Vec<usize> = (0..rows[0].len())
+        .map(|col| {
+            rows.iter()
+                .map(|row| row[col].chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    println!(
+        "{ANSI_BOLD}Day {day}{ANSI_RESET} on {} input(s)",
+        runs.len()
+    );
+    println!("------");
+
+    for row in rows {
+        let line: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .map(|(value, &width)| format!("{value:<width$}"))
+            .collect();
+        println!("{}", line.join("  ").trim_end());
+    }
+}
+
+/// A table cell with the answer and time of a part.
+fn cell(record: Option<&PartRecord>) -> String {
+    let Some(record) = record else {
+        return "✖ did not run".into();
+    };
+
+    let duration = format!("({:.1?})", record.duration);
+
+    match (record.status, &record.answer, &record.error) {
+        (PartStatus::Failed, _, Some(error)) => format!("✖ {error}"),
+        (PartStatus::Solved, Some(answer), _) if answer.contains('\n') => {
+            format!("▼ {} lines {duration}", answer.lines().count())
+        }
+        (PartStatus::Solved, Some(answer), _) => format!("{answer} {duration}"),
+        (PartStatus::Solved, None, _) => format!("✔ {duration}"),
+        _ => "✖ no answer".into(),
+    }
+}
+
+/// One record per line and part, tagged with the name of its input file.
+fn print_json(runs: &[InputRun]) {
+    for run in runs {
+        for record in &run.records {
+            let mut json = JsonValue::from(record);
+            if let Some(map) = json.get_mut::<HashMap<String, JsonValue>>() {
+                map.insert("input".into(), JsonValue::String(run.name.clone()));
+            }
+            println!(
+                "{}",
+                json.stringify()
+                    .expect("part records only contain serializable values")
+            );
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{expected_parts, InputRun};
+    use crate::{
+        day,
+        template::record::{PartRecord, PartStatus},
+        template::PARSE_PART,
+    };
+
+    fn run(parts: &[u8]) -> InputRun {
+        InputRun {
+            name: "input.txt".into(),
+            records: parts
+                .iter()
+                .map(|&part| PartRecord {
+                    day: day!(25),
+                    part,
+                    answer: Some("1".into()),
+                    duration: Duration::ZERO,
+                    samples: 1,
+                    status: PartStatus::Solved,
+                    error: None,
+                    stats: None,
+                    peak_rss: None,
+                    heap: None,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn expects_the_parts_that_ran() {
+        let runs = [run(&[1]), run(&[PARSE_PART, 1])];
+        assert_eq!(expected_parts(None, &runs), vec![PARSE_PART, 1]);
+    }
+
+    #[test]
+    fn flags_inputs_with_missing_parts() {
+        let runs = [run(&[1]), run(&[1, 2])];
+        let parts = expected_parts(None, &runs);
+
+        assert!(run(&[1]).is_flagged(&[1, 2]));
+        assert!(!runs[0].is_flagged(&[1]));
+        assert!(!runs[1].is_flagged(&parts));
+    }
+}
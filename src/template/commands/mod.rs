@@ -1,6 +1,13 @@
 pub mod all;
+pub mod crypt;
 pub mod download;
+mod error;
+pub mod examples;
+pub mod inputs;
 pub mod read;
 pub mod scaffold;
 pub mod solve;
 pub mod time;
+pub mod verify;
+
+pub use error::CommandError;
@@ -1,15 +1,8 @@
-use std::process;
+use crate::template::{aoc_client, commands::CommandError, Day, Year};
 
-use crate::template::{aoc_cli, Day};
-
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
-    }
-
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
-    }
+/// # Errors
+///
+/// Will return an error if the puzzle could not be fetched or written.
+pub fn handle(year: Year, day: Day) -> Result<(), CommandError> {
+    aoc_client::read(year, day).map_err(CommandError::aoc_client("failed to read puzzle"))
 }
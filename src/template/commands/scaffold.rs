@@ -1,10 +1,10 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
-    process,
+    path::Path,
 };
 
-use crate::template::Day;
+use crate::template::{commands::CommandError, config, crypt, paths, Day, Year};
 
 const MODULE_TEMPLATE: &str =
     include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
@@ -20,6 +20,10 @@ fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error>
 }
 
 fn create_file(path: &str) -> Result<File, std::io::Error> {
+    // the data folders of a year are created on first use.
+    if let Some(parent) = Path::new(path).parent() {
+        fs::create_dir_all(parent)?;
+    }
     OpenOptions::new()
         .write(true)
         .create(true)
@@ -27,53 +31,50 @@ fn create_file(path: &str) -> Result<File, std::io::Error> {
         .open(path)
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+/// Create the solution module and empty input and example files of a day.
+///
+/// # Errors
+///
+/// Will return an error if the module exists and `overwrite` is not set, or if a file could not
+/// be written.
+pub fn handle(year: Year, day: Day, overwrite: bool) -> Result<(), CommandError> {
+    let input_path = paths::day_file("inputs", year, day);
+    let example_path = paths::day_file("examples", year, day);
+    let module_path = paths::bin_file(year, day);
 
-    let mut file = match safe_create_file(&module_path, overwrite) {
-        Ok(file) => file,
-        Err(e) => {
-            eprintln!("Failed to create module file: {e}");
-            process::exit(1);
-        }
-    };
+    let mut file = safe_create_file(&module_path, overwrite)
+        .map_err(CommandError::io("Failed to create module file"))?;
 
-    match file.write_all(
+    file.write_all(
         MODULE_TEMPLATE
+            .replace("%YEAR_NUMBER%", &year.into_inner().to_string())
             .replace("%DAY_NUMBER%", &day.into_inner().to_string())
             .as_bytes(),
-    ) {
-        Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
-            process::exit(1);
-        }
-    }
+    )
+    .map_err(CommandError::io("Failed to write module contents"))?;
+    println!("Created module file \"{}\"", &module_path);
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
-        }
+    // an empty placeholder is not needed for inputs that are committed encrypted.
+    let encrypted_path = crypt::encrypted_path(Path::new(&input_path));
+    if encrypted_path.exists() {
+        println!(
+            "Found encrypted input file \"{}\"",
+            encrypted_path.display()
+        );
+    } else {
+        create_file(&input_path).map_err(CommandError::io("Failed to create input file"))?;
+        println!("Created empty input file \"{}\"", &input_path);
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
-        }
-    }
+    create_file(&example_path).map_err(CommandError::io("Failed to create example file"))?;
+    println!("Created empty example file \"{}\"", &example_path);
 
     println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    if config::get().default_year() == Some(year) {
+        println!("🎄 Type `cargo solve {day}` to run your solution.");
+    } else {
+        println!("🎄 Type `cargo solve {day} --year {year}` to run your solution.");
+    }
+
+    Ok(())
 }
@@ -1,13 +1,40 @@
+use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, SystemTime};
+use std::{fs, io};
 
-use crate::template::Day;
+use crate::template::commands::CommandError;
+use crate::template::{crypt, paths, Day, OutputFormat, Year, ANSI_BOLD, ANSI_RESET};
 
-/// # Panics
+/// How often watched files are checked for modifications.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Clears the terminal and moves the cursor to the top left.
+const ANSI_CLEAR: &str = "\x1b[2J\x1b[H";
+
+/// Run the solution bin of a day.
+///
+/// # Errors
 ///
-/// Will panic if the Cargo command spawned by this function fails to start or
-/// panics itself during execution.
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+/// Will return an error if the input does not exist, cargo could not be started, or the
+/// solution bin failed to build or exited with an error.
+pub fn handle(
+    year: Year,
+    day: Day,
+    release: bool,
+    dhat: bool,
+    submit_part: Option<u8>,
+    input: Option<&str>,
+    format: OutputFormat,
+) -> Result<(), CommandError> {
+    check_input(year, day, input)?;
+
+    let mut cmd_args = vec![
+        "run".to_string(),
+        "--bin".to_string(),
+        paths::bin_name(year, day),
+    ];
 
     if dhat {
         cmd_args.extend([
@@ -27,12 +54,164 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push(submit_part.to_string());
     }
 
-    let mut cmd = Command::new("cargo")
+    if let Some(input) = input {
+        // cargo runs in the project root, so relative paths are resolved before.
+        let input = match input {
+            "-" => input.into(),
+            input => std::path::absolute(input)
+                .map_err(CommandError::io(format!(
+                    "Failed to resolve input \"{input}\""
+                )))?
+                .to_string_lossy()
+                .into_owned(),
+        };
+        cmd_args.push("--input".to_string());
+        cmd_args.push(input);
+    }
+
+    if format.is_json() {
+        cmd_args.push("--format".to_string());
+        cmd_args.push(format.to_string());
+    }
+
+    let status = Command::new("cargo")
+        .current_dir(paths::root())
         .args(&cmd_args)
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
-        .spawn()
-        .unwrap();
+        .status()
+        .map_err(CommandError::io("Failed to run cargo"))?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(CommandError::Solution(format!(
+            "Solution of day {day} exited with {status}."
+        )))
+    }
+}
+
+/// Solution bins panic if their input is missing, so report it before building them.
+fn check_input(year: Year, day: Day, input: Option<&str>) -> Result<(), CommandError> {
+    match input {
+        Some("-") => Ok(()),
+        Some(input) if Path::new(input).exists() => Ok(()),
+        Some(input) => Err(CommandError::MissingInput(format!(
+            "Could not find input \"{input}\"."
+        ))),
+        None => {
+            let input_path = paths::day_file("inputs", year, day);
+            let path = Path::new(&input_path);
+
+            if path.exists() || crypt::encrypted_path(path).exists() {
+                Ok(())
+            } else {
+                Err(CommandError::MissingInput(format!(
+                    "Could not find input \"{input_path}\". Run `cargo download {day}` first."
+                )))
+            }
+        }
+    }
+}
+
+/// Re-run a day whenever its solution, input or examples change, until interrupted. The
+/// examples are every `{day}.txt` and `{day}-{name}.txt` in the examples folder.
+///
+/// Each run executes the day's tests on the examples first and only solves the real input if
+/// they pass. Changes are detected by polling file metadata. `input` must be a file, as stdin
+/// can only be read once.
+pub fn watch(year: Year, day: Day, release: bool, input: Option<&str>, format: OutputFormat) {
+    let input_file = PathBuf::from(paths::day_file("inputs", year, day));
+    let mut files = vec![
+        PathBuf::from(paths::bin_file(year, day)),
+        crypt::encrypted_path(&input_file),
+        input_file,
+    ];
+
+    if let Some(input) = input {
+        files.push(PathBuf::from(input));
+    }
+
+    let examples_dir = PathBuf::from(paths::folder_dir("examples", year));
+    let mut last_seen = None;
+
+    loop {
+        // examples are listed on every poll, so that added and removed examples count as changes.
+        let modified: Vec<(PathBuf, Option<SystemTime>)> = files
+            .iter()
+            .cloned()
+            .chain(example_files(&examples_dir, day))
+            .map(|file| {
+                let modified = fs::metadata(&file).and_then(|m| m.modified()).ok();
+                (file, modified)
+            })
+            .collect();
+
+        if last_seen.as_ref() != Some(&modified) {
+            last_seen = Some(modified);
+
+            // keep stdout parseable in JSON mode.
+            if format.is_json() {
+                eprintln!("Watching day {day} (press Ctrl-C to stop)");
+            } else {
+                print!("{ANSI_CLEAR}");
+                println!("{ANSI_BOLD}Watching day {day}{ANSI_RESET} (press Ctrl-C to stop)\n");
+            }
+
+            if run_tests(year, day, format) {
+                if !format.is_json() {
+                    println!();
+                }
+                if let Err(e) = handle(year, day, release, false, None, input, format) {
+                    eprintln!("{e}");
+                }
+            } else {
+                eprintln!("\nTests failed, skipping the real input.");
+            }
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Example files of a day, `{day}.txt` and any `{day}-{name}.txt`, sorted by name.
+fn example_files(dir: &Path, day: Day) -> Vec<PathBuf> {
+    let day = day.to_string();
+
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter(|path| {
+                    let name = path.file_name().and_then(|name| name.to_str());
+                    let stem = name.and_then(|name| name.strip_suffix(".txt"));
+                    stem.is_some_and(|stem| {
+                        stem == day || stem.strip_prefix(&day).is_some_and(|s| s.starts_with('-'))
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    files.sort();
+    files
+}
+
+/// Run the tests of a day, returning whether they passed.
+fn run_tests(year: Year, day: Day, format: OutputFormat) -> bool {
+    let mut command = Command::new("cargo");
+    command.current_dir(paths::root()).args([
+        "test",
+        "--quiet",
+        "--bin",
+        &paths::bin_name(year, day),
+    ]);
+
+    // the test report is not part of the JSON output.
+    if format.is_json() {
+        command.stdout(io::stderr());
+    }
 
-    cmd.wait().unwrap();
+    command.status().is_ok_and(|status| status.success())
 }
@@ -1,19 +1,64 @@
 use std::collections::HashSet;
+use std::path::Path;
 
+use tinyjson::JsonValue;
+
+use crate::template::commands::{all::check_failed_days, CommandError};
+use crate::template::compare::{compare, print_comparisons};
+use crate::template::history::HistoryEntry;
+use crate::template::registry::Solution;
+use crate::template::report;
 use crate::template::run_multi::run_multi;
-use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::timings::{Timing, Timings};
+use crate::template::{
+    all_days, readme_benchmarks, Day, Limits, OutputFormat, Year, ANSI_BOLD, ANSI_RESET,
+};
+
+/// Default percentage by which a part may slow down before `--compare` reports a regression.
+pub const DEFAULT_REGRESSION_THRESHOLD: f64 = 10.0;
 
+/// Bench days and optionally store or compare the results.
+///
+/// When `compare_threshold` is set, results are compared against the stored timings and
+/// the command fails if any part regressed beyond the threshold (in percent). Runs with `heap`
+/// only store their heap stats, keeping the stored durations, and cannot be compared.
+///
+/// # Errors
+///
+/// Will return an error if `heap` is combined with a comparison, a day could not be run, the
+/// timings could not be stored, a part failed or regressed, or a day was aborted.
+///
 /// # Panics
 ///
-/// Will panic if there is an error when trying to write a timings file to disc.
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
+/// Will panic if a comparison could not be serialized to JSON.
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    year: Year,
+    solutions: &[Solution],
+    day: Option<Day>,
+    run_all: bool,
+    store: bool,
+    compare_threshold: Option<f64>,
+    heap: bool,
+    limits: Limits,
+    format: OutputFormat,
+) -> Result<(), CommandError> {
+    // parts run slower under the heap profiler, which would be reported as regressions.
+    if heap && compare_threshold.is_some() {
+        return Err(CommandError::Parse(
+            "`--heap` cannot be combined with `--compare`.".into(),
+        ));
+    }
+
+    let stored_timings = Timings::read_from_file(year);
 
     let days_to_run = day.map_or_else(
         || {
             if run_all {
                 all_days().collect()
+            } else if compare_threshold.is_some() {
+                // when comparing, re-run every day that has a stored baseline.
+                stored_timings.data.iter().map(|t| t.day).collect()
             } else {
                 // when the `--all` flag is not set, filter out days that are fully benched.
                 all_days()
@@ -24,20 +69,148 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
-
-    if store {
-        let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+    let timings = run_multi(
+        year,
+        &days_to_run,
+        solutions,
+        true,
+        true,
+        heap,
+        1,
+        limits,
+        format,
+    )?;
 
-        println!();
-        match readme_benchmarks::update(merged_timings) {
-            Ok(()) => {
-                println!("Stored updated benchmarks.");
+    let comparisons = compare_threshold.map(|threshold| {
+        let comparisons = compare(&stored_timings, &timings);
+        if format.is_json() {
+            for comparison in &comparisons {
+                println!("{}", JsonValue::from(comparison).stringify().unwrap());
             }
-            Err(_) => {
-                eprintln!("Failed to store updated benchmarks.");
+        } else {
+            println!();
+            print_comparisons(&comparisons, threshold);
+        }
+        (comparisons, threshold)
+    });
+
+    if store {
+        // heap runs only store their heap stats, as their durations are not comparable.
+        let merged_timings = if heap {
+            stored_timings.merge_heap(&timings)
+        } else {
+            if let Err(e) = HistoryEntry::now(timings.clone()).append_to_file(year) {
+                eprintln!("Failed to append to timings history: {e}");
             }
+            stored_timings.merge(&timings)
+        };
+        merged_timings
+            .store_file(year)
+            .map_err(CommandError::io("Failed to store timings"))?;
+
+        readme_benchmarks::update(year, merged_timings)?;
+
+        if !format.is_json() {
+            println!();
+            println!("Stored updated benchmarks.");
         }
     }
+
+    if let Some((comparisons, threshold)) = comparisons {
+        let regressions = comparisons
+            .iter()
+            .filter(|c| c.is_regression(threshold))
+            .count();
+
+        if regressions > 0 {
+            return Err(CommandError::Solution(format!(
+                "{regressions} part(s) regressed by more than {threshold}%."
+            )));
+        }
+    }
+
+    check_failed_days(&timings)
+}
+
+/// Print how the runtime of each part of `day` evolved across stored runs.
+///
+/// # Errors
+///
+/// Will return an error if the history could not be read or has no timings of `day`.
+pub fn history(year: Year, day: Day) -> Result<(), CommandError> {
+    let entries: Vec<(HistoryEntry, Timing)> = HistoryEntry::read_all(year)
+        .map_err(CommandError::io("Failed to read the timing history"))?
+        .into_iter()
+        .filter_map(|entry| {
+            let timing = entry.timings.data.iter().find(|t| t.day == day)?.clone();
+            Some((entry, timing))
+        })
+        .collect();
+
+    if entries.is_empty() {
+        return Err(CommandError::MissingInput(format!(
+            "No stored timings for day {day} yet. Run `cargo time {day} --store` first."
+        )));
+    }
+
+    println!("{ANSI_BOLD}{year} Day {day} history{ANSI_RESET}");
+    println!("------");
+
+    let mut previous_nanos: Option<f64> = None;
+
+    for (entry, timing) in entries {
+        let commit = entry
+            .commit
+            .as_deref()
+            .map_or("-------", |c| &c[..c.len().min(7)]);
+
+        let delta = previous_nanos
+            .filter(|nanos| *nanos > 0.0)
+            .map(|nanos| format!(" ({:+.2}%)", (timing.total_nanos - nanos) / nanos * 100.0))
+            .unwrap_or_default();
+
+        // the parse phase is only timed for solutions that declare a parse hook.
+        let parse = timing
+            .parse
+            .as_deref()
+            .map(|parse| format!("Parse: {parse}  "))
+            .unwrap_or_default();
+
+        println!(
+            "{commit}  {}  {parse}Part 1: {}  Part 2: {}{delta}",
+            entry.formatted_timestamp(),
+            timing.part_1.as_deref().unwrap_or("-"),
+            timing.part_2.as_deref().unwrap_or("-"),
+        );
+
+        previous_nanos = Some(timing.total_nanos);
+    }
+
+    Ok(())
+}
+
+/// Export the stored timings as `{path}.csv` and a self-contained `{path}.html` chart.
+///
+/// # Errors
+///
+/// Will return an error if there are no stored timings or the report could not be written.
+pub fn report(year: Year, path: &str) -> Result<(), CommandError> {
+    let timings = Timings::read_from_file(year);
+
+    if timings.data.is_empty() {
+        return Err(CommandError::MissingInput(
+            "No stored timings yet. Run `cargo time --all --store` first.".into(),
+        ));
+    }
+
+    let (csv, html) = report::write(year, &timings, Path::new(path))
+        .map_err(CommandError::io("Failed to write report"))?;
+
+    println!(
+        "Wrote report to \"{}\" and \"{}\"",
+        csv.display(),
+        html.display()
+    );
+
+    Ok(())
 }
@@ -0,0 +1,112 @@
+use crate::template::answers::Answers;
+use crate::template::commands::CommandError;
+use crate::template::record::PartStatus;
+use crate::template::registry::Solution;
+use crate::template::run_multi::{run_day, DayOutput};
+use crate::template::{all_days, Day, Year, ANSI_BOLD, ANSI_RESET, PARSE_PART};
+
+/// Run solved days and check their answers against `data/{year}/answers.json`.
+///
+/// With `record`, the produced answers are stored as the expected answers instead.
+///
+/// # Errors
+///
+/// Will return an error if a day could not be run, the answers could not be read or stored,
+/// any part produces a different answer than expected, or a day with expected answers did not
+/// run.
+pub fn handle(
+    year: Year,
+    solutions: &[Solution],
+    day: Option<Day>,
+    record: bool,
+) -> Result<(), CommandError> {
+    let mut answers = Answers::read_from_file(year)
+        .map_err(CommandError::io("Failed to read expected answers"))?;
+    let mut failures = 0;
+    let mut missing_days = 0;
+
+    let days: Vec<Day> = day.map_or_else(|| all_days().collect(), |day| vec![day]);
+
+    for day in days {
+        let records = run_day(year, day, solutions, true, false, &|output| {
+            if let DayOutput::Stderr(line) = output {
+                eprintln!("{line}");
+            }
+        })?;
+
+        // a day that is not scaffolded or has no input produces no records.
+        if records.is_empty() {
+            if answers.get(day, 1).is_some() || answers.get(day, 2).is_some() {
+                missing_days += 1;
+                println!("Day {day}: {ANSI_BOLD}MISSING{ANSI_RESET} (no solution or input)");
+            }
+            continue;
+        }
+
+        // the parse phase has no answer to check.
+        for part_record in records.into_iter().filter(|r| r.part != PARSE_PART) {
+            let part = part_record.part;
+            let answer = part_record
+                .answer
+                .as_deref()
+                .filter(|_| part_record.status == PartStatus::Solved);
+
+            let verdict = match (answers.get(day, part), answer) {
+                (_, Some(answer)) if record => {
+                    answers.set(day, part, answer);
+                    format!("RECORDED ({})", display(answer))
+                }
+                (Some(expected), Some(answer)) if expected == answer => {
+                    format!("PASS ({})", display(answer))
+                }
+                (Some(expected), answer) => {
+                    failures += 1;
+                    format!(
+                        "{ANSI_BOLD}FAIL{ANSI_RESET} (expected {}, got {})",
+                        display(expected),
+                        answer.map_or_else(|| unanswered(part_record.error.as_deref()), display)
+                    )
+                }
+                (None, Some(answer)) => format!("MISSING (got {})", display(answer)),
+                (None, None) => "MISSING".into(),
+            };
+
+            println!("Day {day} Part {part}: {verdict}");
+        }
+    }
+
+    if record {
+        answers
+            .store_file(year)
+            .map_err(CommandError::io("Failed to store expected answers"))?;
+        println!("Stored expected answers.");
+    }
+
+    if failures > 0 {
+        return Err(CommandError::Solution(format!(
+            "{failures} part(s) did not match the expected answer."
+        )));
+    }
+
+    if missing_days > 0 {
+        return Err(CommandError::Solution(format!(
+            "{missing_days} day(s) with expected answers did not run."
+        )));
+    }
+
+    Ok(())
+}
+
+/// Failed parts show their error instead of an answer.
+fn unanswered(error: Option<&str>) -> String {
+    error.map_or_else(|| "nothing".into(), |error| format!("error: {error}"))
+}
+
+/// Multi-line answers (e.g. ASCII art) are shown on their own lines.
+fn display(answer: &str) -> String {
+    if answer.contains('\n') {
+        format!("▼\n{}\n", answer.trim_end())
+    } else {
+        answer.to_string()
+    }
+}
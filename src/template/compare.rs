@@ -0,0 +1,236 @@
+/// Compares fresh benchmark results against stored timings.
+use std::{collections::HashMap, time::Duration};
+use tinyjson::JsonValue;
+
+use crate::template::record::{part_label, PARSE_PART};
+use crate::template::stats::BenchStats;
+use crate::template::timings::Timings;
+use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+/// Two-sided z-score above which a difference in means is considered significant (~95% confidence).
+const SIGNIFICANCE_Z: f64 = 1.96;
+
+/// Represents the change in runtime of a single part relative to its stored baseline.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartComparison {
+    pub day: Day,
+    pub part: u8,
+    pub baseline: Duration,
+    pub current: Duration,
+    /// Relative change of the median runtime in percent; positive values are slower.
+    pub delta_pct: f64,
+    /// Whether the difference is unlikely to be caused by noise alone.
+    pub significant: bool,
+}
+
+impl PartComparison {
+    /// Build a comparison from the stats of a baseline and a current run.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn new(day: Day, part: u8, baseline: &BenchStats, current: &BenchStats) -> Self {
+        let base_nanos = baseline.median.as_nanos() as f64;
+        let current_nanos = current.median.as_nanos() as f64;
+
+        let delta_pct = if base_nanos == 0.0 {
+            0.0
+        } else {
+            (current_nanos - base_nanos) / base_nanos * 100.0
+        };
+
+        // Welch-style z-test on the means of both sample sets.
+        let variance = |stats: &BenchStats| {
+            (stats.std_dev.as_nanos() as f64).powi(2) / stats.samples.max(1) as f64
+        };
+        let standard_error = (variance(baseline) + variance(current)).sqrt();
+        let mean_delta = current.mean.as_nanos() as f64 - baseline.mean.as_nanos() as f64;
+
+        let significant = if standard_error == 0.0 {
+            mean_delta != 0.0
+        } else {
+            (mean_delta / standard_error).abs() > SIGNIFICANCE_Z
+        };
+
+        Self {
+            day,
+            part,
+            baseline: baseline.median,
+            current: current.median,
+            delta_pct,
+            significant,
+        }
+    }
+
+    /// A part regresses if it became significantly slower by more than `threshold_pct` percent.
+    #[must_use]
+    pub fn is_regression(&self, threshold_pct: f64) -> bool {
+        self.significant && self.delta_pct > threshold_pct
+    }
+}
+
+/// Compare all parts present with benchmark stats in both `baseline` and `current`.
+#[must_use]
+pub fn compare(baseline: &Timings, current: &Timings) -> Vec<PartComparison> {
+    let mut comparisons = vec![];
+
+    for timing in &current.data {
+        let Some(stored) = baseline.data.iter().find(|t| t.day == timing.day) else {
+            continue;
+        };
+
+        let parts = [
+            (PARSE_PART, &stored.parse_stats, &timing.parse_stats),
+            (1, &stored.part_1_stats, &timing.part_1_stats),
+            (2, &stored.part_2_stats, &timing.part_2_stats),
+        ];
+
+        for (part, stored_stats, stats) in parts {
+            if let (Some(stored_stats), Some(stats)) = (stored_stats, stats) {
+                comparisons.push(PartComparison::new(timing.day, part, stored_stats, stats));
+            }
+        }
+    }
+
+    comparisons
+}
+
+pub fn print_comparisons(comparisons: &[PartComparison], threshold_pct: f64) {
+    println!("{ANSI_BOLD}Comparison with stored timings{ANSI_RESET} (threshold: {threshold_pct}%)");
+    println!("------");
+
+    if comparisons.is_empty() {
+        println!("No stored benchmark stats to compare against.");
+        return;
+    }
+
+    for c in comparisons {
+        let verdict = if c.is_regression(threshold_pct) {
+            format!(" {ANSI_BOLD}REGRESSION{ANSI_RESET}")
+        } else if c.significant {
+            format!(" {ANSI_ITALIC}significant{ANSI_RESET}")
+        } else {
+            String::new()
+        };
+
+        println!(
+            "Day {} {}: {:.1?} → {:.1?} ({:+.2}%){verdict}",
+            c.day,
+            part_label(c.part),
+            c.baseline,
+            c.current,
+            c.delta_pct
+        );
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&PartComparison> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &PartComparison) -> Self {
+        let mut map: HashMap<String, Self> = HashMap::new();
+
+        map.insert("day".into(), Self::String(value.day.to_string()));
+        map.insert("part".into(), Self::Number(f64::from(value.part)));
+        map.insert(
+            "baseline_nanos".into(),
+            Self::Number(value.baseline.as_nanos() as f64),
+        );
+        map.insert(
+            "current_nanos".into(),
+            Self::Number(value.current.as_nanos() as f64),
+        );
+        map.insert("delta_pct".into(), Self::Number(value.delta_pct));
+        map.insert("significant".into(), Self::Boolean(value.significant));
+
+        Self::Object(map)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{compare, PartComparison};
+    use crate::{
+        day,
+        template::{
+            stats::BenchStats,
+            timings::{Timing, Timings},
+        },
+    };
+
+    fn stats(median: u64, std_dev: u64) -> BenchStats {
+        BenchStats {
+            samples: 100,
+            mean: Duration::from_nanos(median),
+            median: Duration::from_nanos(median),
+            min: Duration::from_nanos(median),
+            max: Duration::from_nanos(median),
+            std_dev: Duration::from_nanos(std_dev),
+            p95: Duration::from_nanos(median),
+            outliers: 0,
+        }
+    }
+
+    fn timings(part_1: Option<BenchStats>, part_2: Option<BenchStats>) -> Timings {
+        Timings {
+            data: vec![Timing {
+                day: day!(1),
+                parse: None,
+                part_1: part_1.map(|s| format!("{:.1?}", s.median)),
+                part_2: part_2.map(|s| format!("{:.1?}", s.median)),
+                parse_stats: None,
+                part_1_stats: part_1,
+                part_2_stats: part_2,
+                parse_heap: None,
+                part_1_heap: None,
+                part_2_heap: None,
+                failed: vec![],
+                aborted: None,
+                peak_rss: None,
+                total_nanos: 0.0,
+            }],
+        }
+    }
+
+    #[test]
+    fn detects_significant_regressions() {
+        let c = PartComparison::new(day!(1), 1, &stats(1000, 10), &stats(1200, 10));
+        assert!(c.significant);
+        assert!((c.delta_pct - 20.0).abs() < 1e-9);
+        assert!(c.is_regression(10.0));
+        assert!(!c.is_regression(25.0));
+    }
+
+    #[test]
+    fn ignores_noisy_differences() {
+        let c = PartComparison::new(day!(1), 1, &stats(1000, 5000), &stats(1200, 5000));
+        assert!(!c.significant);
+        assert!(!c.is_regression(10.0));
+    }
+
+    #[test]
+    fn improvements_are_not_regressions() {
+        let c = PartComparison::new(day!(1), 2, &stats(1000, 10), &stats(500, 10));
+        assert!(c.significant);
+        assert!((c.delta_pct + 50.0).abs() < 1e-9);
+        assert!(!c.is_regression(10.0));
+    }
+
+    #[test]
+    fn compares_parts_with_stats_only() {
+        let baseline = timings(Some(stats(1000, 10)), None);
+        let current = timings(Some(stats(1100, 10)), Some(stats(1000, 10)));
+        let comparisons = compare(&baseline, &current);
+        assert_eq!(comparisons.len(), 1);
+        assert_eq!(comparisons[0].part, 1);
+    }
+
+    #[test]
+    fn skips_days_missing_from_baseline() {
+        let comparisons = compare(&Timings::default(), &timings(Some(stats(1, 1)), None));
+        assert!(comparisons.is_empty());
+    }
+}
@@ -0,0 +1,518 @@
+/// Project configuration, read from `aoc.toml` in the project root.
+///
+/// Only the subset of TOML needed for flat settings is supported: `[table]` headers, dotted
+/// keys and string, integer and float values. Overrides passed with `--config key=value` are
+/// forwarded to solution bins through the `AOC_CONFIG` env var, one `key = value` per line.
+use std::{env, fmt::Display, fs, io, process, sync::OnceLock, time::Duration};
+
+use crate::template::{commands::CommandError, paths, Year};
+
+pub const CONFIG_FILE_NAME: &str = "aoc.toml";
+
+/// Env var holding `key = value` overrides that apply on top of `aoc.toml`.
+pub const OVERRIDES_ENV: &str = "AOC_CONFIG";
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Config {
+    /// Default event year of all commands, preferred over `AOC_YEAR`.
+    pub year: Option<Year>,
+    pub paths: PathsConfig,
+    pub readme: ReadmeConfig,
+    pub bench: BenchConfig,
+}
+
+/// Locations of the data files. `{year}` is replaced with the year of a command.
+///
+/// Solution bins stay in `src/bin`, where cargo discovers them.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PathsConfig {
+    /// Directory of the timings, answers and ledger of a year.
+    pub data: String,
+    /// Directories of the inputs, examples and puzzles. Default to a folder in `data`.
+    pub inputs: Option<String>,
+    pub examples: Option<String>,
+    pub puzzles: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReadmeConfig {
+    /// README that `cargo time --store` writes the benchmarks to.
+    pub path: String,
+    /// Marker around the benchmark table shared by all years.
+    pub marker: String,
+    /// Marker around the benchmark table of a single year.
+    pub year_marker: String,
+}
+
+/// Budget of `cargo time`. Each part is sampled for about `budget`, bounded by the number of
+/// samples.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BenchConfig {
+    pub budget: Duration,
+    pub min_samples: u128,
+    pub max_samples: u128,
+}
+
+impl Default for PathsConfig {
+    fn default() -> Self {
+        Self {
+            data: "data/{year}".into(),
+            inputs: None,
+            examples: None,
+            puzzles: None,
+        }
+    }
+}
+
+impl Default for ReadmeConfig {
+    fn default() -> Self {
+        Self {
+            path: "README.md".into(),
+            marker: "<!--- benchmarking table --->".into(),
+            year_marker: "<!--- benchmarking table {year} --->".into(),
+        }
+    }
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self {
+            budget: Duration::from_secs(1),
+            min_samples: 10,
+            max_samples: 10_000,
+        }
+    }
+}
+
+impl Config {
+    /// Read `aoc.toml` from the project root if present and apply the overrides of
+    /// [`OVERRIDES_ENV`].
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the file cannot be read or contains invalid settings.
+    pub fn load() -> Result<Self, ConfigError> {
+        let mut config = match fs::read_to_string(paths::resolve(CONFIG_FILE_NAME)) {
+            Ok(s) => Self::parse_settings(&s)?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Self::default(),
+            Err(e) => return Err(ConfigError(format!("{CONFIG_FILE_NAME}: {e}"))),
+        };
+
+        if let Ok(overrides) = env::var(OVERRIDES_ENV) {
+            for line in overrides.lines().filter(|line| !line.trim().is_empty()) {
+                config.apply_override(line)?;
+            }
+        }
+
+        config.validate()
+    }
+
+    /// Parse the contents of a config file.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if a line is not valid, a key is unknown or a value has the wrong type.
+    pub fn parse(s: &str) -> Result<Self, ConfigError> {
+        Self::parse_settings(s)?.validate()
+    }
+
+    /// Parse the settings of a config file, which are only validated once overrides apply.
+    fn parse_settings(s: &str) -> Result<Self, ConfigError> {
+        let mut config = Self::default();
+        let mut table = String::new();
+
+        for (index, line) in s.lines().enumerate() {
+            let at_line = |message: String| {
+                ConfigError(format!("{CONFIG_FILE_NAME}:{}: {message}", index + 1))
+            };
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if let Some(header) = line.strip_prefix('[') {
+                let header = strip_comment(header).trim_end();
+                table = header
+                    .strip_suffix(']')
+                    .map(|name| format!("{}.", name.trim()))
+                    .ok_or_else(|| at_line("expected `]` after a table name.".into()))?;
+                continue;
+            }
+
+            let (key, value) = split_key_value(line).map_err(at_line)?;
+            let value = Value::parse(value).map_err(at_line)?;
+            config
+                .set(&format!("{table}{key}"), value)
+                .map_err(at_line)?;
+        }
+
+        Ok(config)
+    }
+
+    /// Apply a single `key = value` override. Values that are not valid TOML are read as
+    /// strings, so that `--config paths.data=data` works without quoting.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the key is unknown or the value has the wrong type.
+    pub fn apply_override(&mut self, line: &str) -> Result<(), ConfigError> {
+        let at_override = |message: String| ConfigError(format!("--config `{line}`: {message}"));
+
+        let (key, value) = split_key_value(line).map_err(at_override)?;
+        let value = Value::parse(value).unwrap_or_else(|_| Value::String(value.into()));
+        self.set(key, value).map_err(at_override)
+    }
+
+    /// Default year of commands: `year` of the config, then `AOC_YEAR`.
+    #[must_use]
+    pub fn default_year(&self) -> Option<Year> {
+        self.year.or_else(Year::from_env)
+    }
+
+    fn set(&mut self, key: &str, value: Value) -> Result<(), String> {
+        match key {
+            "year" => {
+                let year = u16::try_from(value.integer()?).ok().and_then(Year::new);
+                self.year = Some(year.ok_or("expected a year of 2015 or later.")?);
+            }
+            "paths.data" => self.paths.data = value.string()?,
+            "paths.inputs" => self.paths.inputs = Some(value.string()?),
+            "paths.examples" => self.paths.examples = Some(value.string()?),
+            "paths.puzzles" => self.paths.puzzles = Some(value.string()?),
+            "readme.path" => self.readme.path = value.string()?,
+            "readme.marker" => self.readme.marker = value.string()?,
+            "readme.year_marker" => self.readme.year_marker = value.string()?,
+            "bench.budget" => {
+                self.bench.budget = Duration::try_from_secs_f64(value.float()?)
+                    .map_err(|_| "expected a positive number of seconds.")?;
+            }
+            "bench.min_samples" => self.bench.min_samples = value.samples()?,
+            "bench.max_samples" => self.bench.max_samples = value.samples()?,
+            _ => return Err(format!("unknown key `{key}`.")),
+        }
+        Ok(())
+    }
+
+    fn validate(self) -> Result<Self, ConfigError> {
+        if self.bench.min_samples > self.bench.max_samples {
+            return Err(ConfigError(
+                "bench.min_samples must not be larger than bench.max_samples.".into(),
+            ));
+        }
+        if self.readme.marker.is_empty() || self.readme.year_marker.is_empty() {
+            return Err(ConfigError("README markers must not be empty.".into()));
+        }
+        Ok(self)
+    }
+}
+
+impl PathsConfig {
+    /// Directory of the data files of a year, e.g. `data/2021`.
+    #[must_use]
+    pub fn data_dir(&self, year: Year) -> String {
+        expand(&self.data, year)
+    }
+
+    /// Directory of a data folder of a year, e.g. `data/2021/inputs`.
+    #[must_use]
+    pub fn folder(&self, folder: &str, year: Year) -> String {
+        let template = match folder {
+            "inputs" => self.inputs.as_ref(),
+            "examples" => self.examples.as_ref(),
+            "puzzles" => self.puzzles.as_ref(),
+            _ => None,
+        };
+
+        template.map_or_else(
+            || format!("{}/{folder}", self.data_dir(year)),
+            |template| expand(template, year),
+        )
+    }
+}
+
+impl ReadmeConfig {
+    /// Marker of the table of `year`.
+    #[must_use]
+    pub fn year_marker(&self, year: Year) -> String {
+        expand(&self.year_marker, year)
+    }
+}
+
+/// Load the configuration on first use.
+///
+/// # Errors
+///
+/// Will return an error if the configuration is invalid. It is loaded again on the next call.
+pub fn init() -> Result<&'static Config, ConfigError> {
+    if let Some(config) = CONFIG.get() {
+        return Ok(config);
+    }
+    let config = Config::load()?;
+    Ok(CONFIG.get_or_init(|| config))
+}
+
+/// The configuration of the project. Exits with the exit code of a parse error if it is
+/// invalid, which the CLI reports up front via [`init`].
+#[must_use]
+pub fn get() -> &'static Config {
+    init().unwrap_or_else(|e| {
+        let error = CommandError::Parse(e.to_string());
+        eprintln!("{error}");
+        process::exit(error.exit_code());
+    })
+}
+
+/// Add `key=value` overrides for this process and the solution bins it spawns.
+pub fn add_overrides(overrides: &[String]) {
+    if overrides.is_empty() {
+        return;
+    }
+
+    let mut lines: Vec<String> = env::var(OVERRIDES_ENV).into_iter().collect();
+    lines.extend(overrides.iter().cloned());
+    env::set_var(OVERRIDES_ENV, lines.join("\n"));
+}
+
+#[allow(clippy::literal_string_with_formatting_args)]
+fn expand(template: &str, year: Year) -> String {
+    template.replace("{year}", &year.to_string())
+}
+
+fn split_key_value(line: &str) -> Result<(&str, &str), String> {
+    let (key, value) = line
+        .split_once('=')
+        .ok_or("expected `key = value` or a `[table]` header.")?;
+    let key = key.trim();
+
+    if key.is_empty()
+        || !key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "_-.".contains(c))
+    {
+        return Err(format!("invalid key `{key}`."));
+    }
+
+    Ok((key, value.trim()))
+}
+
+/// Remove a trailing `# comment` from an unquoted part of a line.
+fn strip_comment(s: &str) -> &str {
+    s.split_once('#').map_or(s, |(before, _)| before)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A value of the supported TOML subset.
+#[derive(Clone, Debug, PartialEq)]
+enum Value {
+    String(String),
+    Integer(i64),
+    Float(f64),
+}
+
+impl Value {
+    fn parse(s: &str) -> Result<Self, String> {
+        if let Some(rest) = s.strip_prefix('"') {
+            return Self::parse_quoted(rest, '"');
+        }
+        if let Some(rest) = s.strip_prefix('\'') {
+            return Self::parse_quoted(rest, '\'');
+        }
+
+        let s = strip_comment(s).trim();
+        let number = s.replace('_', "");
+
+        number
+            .parse()
+            .map(Self::Integer)
+            .or_else(|_| number.parse().map(Self::Float))
+            .map_err(|_| format!("expected a string or a number, found `{s}`."))
+    }
+
+    /// Parse a string after its opening quote. Only basic strings support escapes.
+    fn parse_quoted(s: &str, quote: char) -> Result<Self, String> {
+        let mut value = String::new();
+        let mut chars = s.char_indices();
+
+        while let Some((i, c)) = chars.next() {
+            match c {
+                c if c == quote => {
+                    let rest = strip_comment(&s[i + 1..]).trim();
+                    return if rest.is_empty() {
+                        Ok(Self::String(value))
+                    } else {
+                        Err(format!("unexpected `{rest}` after string."))
+                    };
+                }
+                '\\' if quote == '"' => match chars.next().map(|(_, c)| c) {
+                    Some('n') => value.push('\n'),
+                    Some('t') => value.push('\t'),
+                    Some(c @ ('"' | '\\')) => value.push(c),
+                    _ => return Err("unsupported escape sequence.".into()),
+                },
+                c => value.push(c),
+            }
+        }
+
+        Err("unterminated string.".into())
+    }
+
+    fn string(self) -> Result<String, String> {
+        match self {
+            Self::String(s) => Ok(s),
+            _ => Err("expected a string.".into()),
+        }
+    }
+
+    fn integer(self) -> Result<i64, String> {
+        match self {
+            Self::Integer(i) => Ok(i),
+            _ => Err("expected an integer.".into()),
+        }
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn float(self) -> Result<f64, String> {
+        match self {
+            Self::Integer(i) => Ok(i as f64),
+            Self::Float(f) => Ok(f),
+            Self::String(_) => Err("expected a number.".into()),
+        }
+    }
+
+    fn samples(self) -> Result<u128, String> {
+        self.integer()
+            .ok()
+            .and_then(|i| u128::try_from(i).ok())
+            .filter(|&samples| samples > 0)
+            .ok_or_else(|| "expected a positive integer.".into())
+    }
+}
+
+/// An error in `aoc.toml` or a `--config` override.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ConfigError(String);
+
+impl std::error::Error for ConfigError {}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{Config, Value};
+    use crate::year;
+
+    #[test]
+    fn defaults_to_the_template_layout() {
+        let config = Config::parse("# nothing configured\n").unwrap();
+        assert_eq!(config, Config::default());
+        assert_eq!(config.paths.data_dir(year!(2021)), "data/2021");
+        assert_eq!(
+            config.paths.folder("inputs", year!(2021)),
+            "data/2021/inputs"
+        );
+        assert_eq!(
+            config.readme.year_marker(year!(2021)),
+            "<!--- benchmarking table 2021 --->"
+        );
+    }
+
+    #[test]
+    fn parses_config_files() {
+        let config = Config::parse(
+            r#"
+year = 2022
+
+[paths]
+data = "aoc/{year}"   # timings and answers
+inputs = '../private/{year}'
+
+[readme]
+path = "docs/BENCHMARKS.md"
+
+[bench]
+budget = 0.5
+min_samples = 5
+max_samples = 1_000
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.year, Some(year!(2022)));
+        assert_eq!(
+            config.paths.folder("inputs", year!(2022)),
+            "../private/2022"
+        );
+        assert_eq!(
+            config.paths.folder("examples", year!(2022)),
+            "aoc/2022/examples"
+        );
+        assert_eq!(config.readme.path, "docs/BENCHMARKS.md");
+        assert_eq!(config.bench.budget, Duration::from_millis(500));
+        assert_eq!(config.bench.min_samples, 5);
+        assert_eq!(config.bench.max_samples, 1000);
+    }
+
+    #[test]
+    fn parses_values() {
+        assert_eq!(
+            Value::parse(r#""a \"b\"" # c"#),
+            Ok(Value::String("a \"b\"".into()))
+        );
+        assert_eq!(
+            Value::parse(r"'C:\data'"),
+            Ok(Value::String(r"C:\data".into()))
+        );
+        assert_eq!(Value::parse("10_000"), Ok(Value::Integer(10_000)));
+        assert_eq!(Value::parse("1.5 # s"), Ok(Value::Float(1.5)));
+        assert!(Value::parse("\"open").is_err());
+        assert!(Value::parse("data").is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_config_files() {
+        let error = |s: &str| Config::parse(s).unwrap_err().to_string();
+
+        assert_eq!(
+            error("[paths]\ndta = \"data\""),
+            "aoc.toml:2: unknown key `paths.dta`."
+        );
+        assert_eq!(error("year = \"2021\""), "aoc.toml:1: expected an integer.");
+        assert_eq!(
+            error("year = 2014"),
+            "aoc.toml:1: expected a year of 2015 or later."
+        );
+        assert_eq!(
+            error("[bench\nbudget = 1"),
+            "aoc.toml:1: expected `]` after a table name."
+        );
+        assert_eq!(
+            error("[bench]\nmin_samples = 0"),
+            "aoc.toml:2: expected a positive integer."
+        );
+        assert!(error("[bench]\nmin_samples = 100\nmax_samples = 10").contains("min_samples"));
+    }
+
+    #[test]
+    fn applies_overrides() {
+        let mut config = Config::default();
+        config.apply_override("paths.data=other/{year}").unwrap();
+        config.apply_override("bench.budget = 2").unwrap();
+        assert_eq!(config.paths.data_dir(year!(2021)), "other/2021");
+        assert_eq!(config.bench.budget, Duration::from_secs(2));
+
+        assert!(config.apply_override("bench.budget=fast").is_err());
+        assert!(config.apply_override("unknown=1").is_err());
+    }
+}
@@ -0,0 +1,214 @@
+/// Encryption of puzzle inputs at rest.
+///
+/// Inputs are stored next to their plain path as `NN.txt.enc` blobs, which hold a magic
+/// header, a random nonce and the ChaCha20-Poly1305 ciphertext. The key is held locally in
+/// `AOC_INPUT_KEY` or the `.input-key` file in the project root and never committed.
+use std::{
+    env,
+    fmt::{Display, Write},
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use chacha20poly1305::{
+    aead::{Aead, AeadCore, KeyInit, OsRng},
+    ChaCha20Poly1305, Nonce,
+};
+
+use crate::template::paths;
+
+pub const KEY_FILE_NAME: &str = ".input-key";
+
+static MAGIC: &[u8] = b"AOCENC1\n";
+const NONCE_LEN: usize = 12;
+const KEY_LEN: usize = 32;
+
+#[derive(Debug)]
+pub enum CryptError {
+    KeyNotFound,
+    InvalidKey,
+    InvalidBlob,
+    Decryption,
+    IO(io::Error),
+}
+
+impl Display for CryptError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::KeyNotFound => write!(
+                f,
+                "no input key found. Set `AOC_INPUT_KEY` or create one with `cargo crypt keygen`."
+            ),
+            Self::InvalidKey => write!(f, "the input key is not {} hex characters.", KEY_LEN * 2),
+            Self::InvalidBlob => write!(f, "the file is not an encrypted input."),
+            Self::Decryption => write!(f, "the input could not be decrypted with this key."),
+            Self::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for CryptError {}
+
+impl From<io::Error> for CryptError {
+    fn from(e: io::Error) -> Self {
+        Self::IO(e)
+    }
+}
+
+/// A key used to encrypt and decrypt inputs.
+pub struct InputKey([u8; KEY_LEN]);
+
+impl InputKey {
+    /// Create a new random key.
+    #[must_use]
+    pub fn generate() -> Self {
+        Self(ChaCha20Poly1305::generate_key(&mut OsRng).into())
+    }
+
+    /// Read the key from `AOC_INPUT_KEY` or the key file, returning `None` if neither is set.
+    ///
+    /// # Errors
+    ///
+    /// Will return `CryptError` if the configured key is malformed.
+    pub fn from_env() -> Result<Option<Self>, CryptError> {
+        let key = match env::var("AOC_INPUT_KEY") {
+            Ok(key) => key,
+            Err(_) => match fs::read_to_string(paths::resolve(KEY_FILE_NAME)) {
+                Ok(key) => key,
+                Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+                Err(e) => return Err(e.into()),
+            },
+        };
+
+        key.parse().map(Some)
+    }
+
+    /// Encrypt `plaintext` with a random nonce.
+    #[must_use]
+    pub fn encrypt(&self, plaintext: &[u8]) -> Vec<u8> {
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = self
+            .cipher()
+            .encrypt(&nonce, plaintext)
+            .expect("encrypting into a vec does not fail");
+
+        [MAGIC, nonce.as_slice(), &ciphertext].concat()
+    }
+
+    /// Decrypt a blob created by [`InputKey::encrypt`].
+    ///
+    /// # Errors
+    ///
+    /// Will return `CryptError` if the blob is malformed or was encrypted with another key.
+    pub fn decrypt(&self, blob: &[u8]) -> Result<Vec<u8>, CryptError> {
+        let payload = blob.strip_prefix(MAGIC).ok_or(CryptError::InvalidBlob)?;
+
+        if payload.len() < NONCE_LEN {
+            return Err(CryptError::InvalidBlob);
+        }
+
+        let (nonce, ciphertext) = payload.split_at(NONCE_LEN);
+        self.cipher()
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| CryptError::Decryption)
+    }
+
+    fn cipher(&self) -> ChaCha20Poly1305 {
+        ChaCha20Poly1305::new(&self.0.into())
+    }
+}
+
+impl Display for InputKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut hex = String::with_capacity(KEY_LEN * 2);
+        for byte in self.0 {
+            let _ = write!(hex, "{byte:02x}");
+        }
+        f.write_str(&hex)
+    }
+}
+
+impl std::str::FromStr for InputKey {
+    type Err = CryptError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.len() != KEY_LEN * 2 || !s.is_ascii() {
+            return Err(CryptError::InvalidKey);
+        }
+
+        let mut key = [0; KEY_LEN];
+        for (i, byte) in key.iter_mut().enumerate() {
+            *byte =
+                u8::from_str_radix(&s[i * 2..i * 2 + 2], 16).map_err(|_| CryptError::InvalidKey)?;
+        }
+
+        Ok(Self(key))
+    }
+}
+
+/// Path of the encrypted blob belonging to a plain file, e.g. `data/2021/inputs/01.txt.enc`.
+#[must_use]
+pub fn encrypted_path(path: &Path) -> PathBuf {
+    let mut path = path.as_os_str().to_owned();
+    path.push(".enc");
+    PathBuf::from(path)
+}
+
+/// Read and decrypt the encrypted blob belonging to the plain file at `path`.
+///
+/// # Errors
+///
+/// Will return `CryptError` if no key is configured, or the blob cannot be read or decrypted.
+pub fn read_encrypted(path: &Path) -> Result<String, CryptError> {
+    let key = InputKey::from_env()?.ok_or(CryptError::KeyNotFound)?;
+    let plaintext = key.decrypt(&fs::read(encrypted_path(path))?)?;
+    String::from_utf8(plaintext).map_err(|_| CryptError::InvalidBlob)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::Path;
+
+    use super::{encrypted_path, CryptError, InputKey};
+
+    #[test]
+    fn roundtrips_inputs() {
+        let key = InputKey::generate();
+        let blob = key.encrypt(b"1721\n979\n366\n");
+        assert!(!blob.windows(4).any(|w| w == b"1721"));
+        assert_eq!(key.decrypt(&blob).unwrap(), b"1721\n979\n366\n");
+    }
+
+    #[test]
+    fn rejects_other_keys_and_blobs() {
+        let blob = InputKey::generate().encrypt(b"secret");
+        assert!(matches!(
+            InputKey::generate().decrypt(&blob),
+            Err(CryptError::Decryption)
+        ));
+        assert!(matches!(
+            InputKey::generate().decrypt(b"plain text"),
+            Err(CryptError::InvalidBlob)
+        ));
+    }
+
+    #[test]
+    fn parses_hex_keys() {
+        let key = InputKey::generate();
+        let parsed: InputKey = format!("{key}\n").parse().unwrap();
+        assert_eq!(parsed.to_string(), key.to_string());
+        assert!("abc".parse::<InputKey>().is_err());
+        assert!("zz".repeat(32).parse::<InputKey>().is_err());
+    }
+
+    #[test]
+    fn appends_extension() {
+        assert_eq!(
+            encrypted_path(Path::new("data/2021/inputs/01.txt")),
+            Path::new("data/2021/inputs/01.txt.enc")
+        );
+    }
+}
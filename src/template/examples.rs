@@ -0,0 +1,240 @@
+//! Extraction of example inputs from downloaded puzzle descriptions.
+//!
+//! Puzzles are stored as markdown, where every `<pre>` block of the puzzle page becomes a fenced
+//! code block. Examples are usually introduced by a sentence mentioning "example".
+//!
+//! Example files may start with a header that declares the expected answers, which is used by
+//! [`example_tests!`](crate::example_tests) and stripped when the example is read:
+//!
+//! ```text
+//! +++
+//! part_1 = 5
+//! part_2 = 12
+//! +++
+//! 0,9 -> 5,9
+//! ```
+//!
+//! `cargo examples` declares the parts without answers, so their tests fail until the expected
+//! answers are filled in.
+
+use std::fmt::Write;
+
+/// Opening and closing line of the header of an example file.
+const HEADER_FENCE: &str = "+++";
+
+/// A fenced code block of a puzzle description.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CodeBlock {
+    /// The part of the puzzle whose description contains the block.
+    pub part: u8,
+    pub content: String,
+    /// Whether the text leading up to the block mentions an example.
+    pub follows_example: bool,
+}
+
+impl CodeBlock {
+    pub fn line_count(&self) -> usize {
+        self.content.lines().count()
+    }
+}
+
+/// Collect the code blocks of a puzzle description, in order.
+pub fn code_blocks(markdown: &str) -> Vec<CodeBlock> {
+    let mut blocks = vec![];
+    let mut part = 1;
+    let mut follows_example = false;
+    let mut current: Option<Vec<&str>> = None;
+
+    for line in markdown.lines() {
+        if line.trim_end() == "```" {
+            match current.take() {
+                Some(lines) => {
+                    let mut content = lines.join("\n");
+                    content.push('\n');
+                    blocks.push(CodeBlock {
+                        part,
+                        content,
+                        follows_example,
+                    });
+                    follows_example = false;
+                }
+                None => current = Some(vec![]),
+            }
+        } else if let Some(lines) = &mut current {
+            lines.push(line);
+        } else if line.starts_with("## ") && line.contains("Part Two") {
+            part = 2;
+            follows_example = false;
+        } else if line.to_lowercase().contains("example") {
+            follows_example = true;
+        }
+    }
+
+    blocks
+}
+
+/// Index of the block that most likely holds the example input of a part.
+///
+/// Prefers the first block introduced as an example, then the first block spanning several
+/// lines, then the first block of the part.
+pub fn likely_example(blocks: &[CodeBlock], part: u8) -> Option<usize> {
+    let candidates = || blocks.iter().enumerate().filter(|(_, b)| b.part == part);
+
+    candidates()
+        .find(|(_, b)| b.follows_example)
+        .or_else(|| candidates().find(|(_, b)| b.line_count() > 1))
+        .or_else(|| candidates().next())
+        .map(|(i, _)| i)
+}
+
+/// Split an example file into the answers declared in its header and the example input.
+///
+/// Parts declared without an answer, e.g. `part_2 =`, are left out.
+pub fn split_header(text: &str) -> (Vec<(u8, String)>, &str) {
+    parse_header(text).map_or((vec![], text), |(declared, offset)| {
+        let answers = declared
+            .into_iter()
+            .filter(|(_, value)| !value.is_empty())
+            .map(|(part, value)| (part, value.to_string()))
+            .collect();
+        (answers, &text[offset..])
+    })
+}
+
+/// Parts declared in the header of an example file, with or without an answer.
+// NOTE: used by the build script to generate the example tests.
+#[allow(dead_code)]
+pub fn declared_parts(text: &str) -> Vec<u8> {
+    parse_header(text).map_or_else(Vec::new, |(declared, _)| {
+        declared.into_iter().map(|(part, _)| part).collect()
+    })
+}
+
+/// The `part_N = value` lines of a header and the offset of the input that follows it.
+fn parse_header(text: &str) -> Option<(Vec<(u8, &str)>, usize)> {
+    let mut lines = text.split_inclusive('\n');
+
+    let opening = lines
+        .next()
+        .filter(|line| line.trim_end() == HEADER_FENCE)?;
+
+    let mut declared = vec![];
+    let mut offset = opening.len();
+
+    for line in lines {
+        offset += line.len();
+
+        if line.trim_end() == HEADER_FENCE {
+            return Some((declared, offset));
+        }
+
+        let part = line.split_once('=').and_then(|(key, value)| {
+            let part = key.trim().strip_prefix("part_")?.parse().ok()?;
+            Some((part, value.trim().trim_matches('"')))
+        });
+
+        declared.extend(part);
+    }
+
+    // an unterminated header is treated as part of the input.
+    None
+}
+
+/// A header declaring `parts` without answers, to be filled in once they are known.
+pub fn empty_header(parts: &[u8]) -> String {
+    let mut header = format!("{HEADER_FENCE}\n");
+    for part in parts {
+        let _ = writeln!(header, "part_{part} =");
+    }
+    header + HEADER_FENCE + "\n"
+}
+
+/// Remove the header of an example file, if it has one.
+pub fn strip_header(text: String) -> String {
+    if text.starts_with(HEADER_FENCE) {
+        split_header(&text).1.to_string()
+    } else {
+        text
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{code_blocks, declared_parts, empty_header, likely_example, split_header};
+
+    const PUZZLE: &str = "## --- Day 1: Test ---
+
+The answer is `42`.
+
+```
+not an example
+```
+
+For example, suppose you have the following list:
+
+```
+1721
+979
+```
+
+In this list, the two entries that sum to `2020` are `1721` and `299`.
+
+## --- Part Two ---
+
+Consider the following example instead:
+
+```
+1
+2
+3
+```
+";
+
+    #[test]
+    fn collects_blocks_per_part() {
+        let blocks = code_blocks(PUZZLE);
+        assert_eq!(blocks.len(), 3);
+        assert_eq!(blocks[1].content, "1721\n979\n");
+        assert_eq!(blocks[1].part, 1);
+        assert!(blocks[1].follows_example);
+        assert!(!blocks[0].follows_example);
+        assert_eq!(blocks[2].part, 2);
+        assert_eq!(blocks[2].line_count(), 3);
+    }
+
+    #[test]
+    fn picks_likely_examples() {
+        let blocks = code_blocks(PUZZLE);
+        assert_eq!(likely_example(&blocks, 1), Some(1));
+        assert_eq!(likely_example(&blocks, 2), Some(2));
+        assert_eq!(likely_example(&blocks[..2], 2), None);
+        assert_eq!(likely_example(&blocks[..1], 1), Some(0));
+    }
+
+    #[test]
+    fn splits_headers() {
+        let (answers, input) = split_header("+++\npart_1 = 5\npart_2 = \"ab c\"\n+++\n1,2\n");
+        assert_eq!(answers, vec![(1, "5".into()), (2, "ab c".into())]);
+        assert_eq!(input, "1,2\n");
+
+        let (answers, input) = split_header("+++\npart_2 =\n+++\n#.#\n");
+        assert!(answers.is_empty());
+        assert_eq!(input, "#.#\n");
+
+        assert_eq!(split_header("#.#\n").1, "#.#\n");
+        assert_eq!(split_header("+++\npart_1 = 5\n").1, "+++\npart_1 = 5\n");
+    }
+
+    #[test]
+    fn declares_parts_without_answers() {
+        let text = format!("{}1,2\n", empty_header(&[1, 2]));
+        assert_eq!(text, "+++\npart_1 =\npart_2 =\n+++\n1,2\n");
+        assert_eq!(declared_parts(&text), vec![1, 2]);
+        assert_eq!(split_header(&text), (vec![], "1,2\n"));
+
+        assert_eq!(declared_parts("+++\npart_2 = 5\n+++\n"), vec![2]);
+        assert!(declared_parts("1,2\n").is_empty());
+    }
+}
@@ -0,0 +1,309 @@
+/// Append-only history of stored benchmark runs, keyed by git commit.
+use std::{
+    collections::HashMap,
+    fs::{self, OpenOptions},
+    io::{Error, ErrorKind, Write},
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::timings::{Timing, Timings};
+use crate::template::{paths, Year};
+
+static HISTORY_FILE_NAME: &str = "timings-history.jsonl";
+static GIT_DIR_NAME: &str = ".git";
+
+/// Represents the timings stored by a single `cargo time --store` run.
+#[derive(Clone, Debug)]
+pub struct HistoryEntry {
+    /// Hash of the checked out commit, if the project is a git repository.
+    pub commit: Option<String>,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    pub timings: Timings,
+}
+
+impl HistoryEntry {
+    /// Create an entry for the current commit and time.
+    #[must_use]
+    pub fn now(timings: Timings) -> Self {
+        Self {
+            commit: git_dir(&paths::resolve(GIT_DIR_NAME)).and_then(|dir| current_commit(&dir)),
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+            timings,
+        }
+    }
+
+    /// Append the entry as a single JSON line to the history file.
+    pub fn append_to_file(&self, year: Year) -> Result<(), Error> {
+        let line = JsonValue::from(self)
+            .stringify()
+            .map_err(|e| Error::other(e.message().to_string()))?;
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(paths::data_file(year, HISTORY_FILE_NAME))?;
+
+        writeln!(file, "{line}")
+    }
+
+    /// Read all entries from the history file, oldest first. Malformed lines are skipped and a
+    /// missing file has no entries.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the history file exists but could not be read.
+    pub fn read_all(year: Year) -> Result<Vec<Self>, Error> {
+        match fs::read_to_string(paths::data_file(year, HISTORY_FILE_NAME)) {
+            Ok(s) => Ok(s
+                .lines()
+                .filter_map(|line| Self::try_from(line).ok())
+                .collect()),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(vec![]),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Format the timestamp as `YYYY-MM-DD HH:MM UTC`.
+    #[must_use]
+    pub fn formatted_timestamp(&self) -> String {
+        let days = self.timestamp / 86_400;
+        let secs = self.timestamp % 86_400;
+        let (year, month, day) = civil_from_days(days);
+        format!(
+            "{year:04}-{month:02}-{day:02} {:02}:{:02} UTC",
+            secs / 3600,
+            (secs % 3600) / 60
+        )
+    }
+}
+
+/// Resolve the git dir of a checkout. In worktrees and submodules, `.git` is a file that points
+/// to the git dir with a `gitdir:` line, relative to the checkout if not absolute.
+fn git_dir(dot_git: &Path) -> Option<PathBuf> {
+    if dot_git.is_dir() {
+        return Some(dot_git.to_path_buf());
+    }
+
+    let pointer = fs::read_to_string(dot_git).ok()?;
+    let target = pointer.trim().strip_prefix("gitdir:")?.trim();
+    Some(dot_git.parent()?.join(target))
+}
+
+/// Resolve the commit hash `HEAD` points to, following a symbolic ref if needed.
+fn current_commit(git_dir: &Path) -> Option<String> {
+    let head = fs::read_to_string(git_dir.join("HEAD")).ok()?;
+    let head = head.trim();
+
+    let Some(reference) = head.strip_prefix("ref: ") else {
+        return Some(head.to_string());
+    };
+
+    // worktrees keep their own `HEAD`, but share the refs of the main git dir.
+    let common_dir = fs::read_to_string(git_dir.join("commondir"))
+        .map_or_else(|_| git_dir.to_path_buf(), |dir| git_dir.join(dir.trim()));
+
+    for dir in [git_dir, &common_dir] {
+        if let Ok(hash) = fs::read_to_string(dir.join(reference)) {
+            return Some(hash.trim().to_string());
+        }
+    }
+
+    // refs may have been packed by `git gc`.
+    fs::read_to_string(common_dir.join("packed-refs"))
+        .ok()?
+        .lines()
+        .filter(|line| !line.starts_with('#') && !line.starts_with('^'))
+        .find_map(|line| {
+            let (hash, name) = line.split_once(' ')?;
+            (name == reference).then(|| hash.to_string())
+        })
+}
+
+/// Convert days since the unix epoch to a (year, month, day) civil date.
+/// See: <https://howardhinnant.github.io/date_algorithms.html#civil_from_days>
+const fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&HistoryEntry> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &HistoryEntry) -> Self {
+        let mut map: HashMap<String, Self> = HashMap::new();
+
+        map.insert(
+            "commit".into(),
+            value.commit.clone().map_or(Self::Null, Self::String),
+        );
+        map.insert("timestamp".into(), Self::Number(value.timestamp as f64));
+        map.insert(
+            "data".into(),
+            Self::Array(value.timings.data.iter().map(Self::from).collect()),
+        );
+
+        Self::Object(map)
+    }
+}
+
+impl TryFrom<&str> for HistoryEntry {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(value).or(Err("not valid JSON."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected history entry to be a JSON object.")?;
+
+        let commit = json
+            .get("commit")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected entry.commit to be null or string.")?;
+
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected entry.timestamp to be a number.")?;
+
+        let data = json
+            .get("data")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("Expected entry.data to be an array.")?
+            .iter()
+            .map(Timing::try_from)
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            commit: commit.cloned(),
+            timestamp: timestamp as u64,
+            timings: Timings { data },
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{env, fs};
+    use tinyjson::JsonValue;
+
+    use super::{civil_from_days, current_commit, git_dir, HistoryEntry};
+    use crate::{
+        day,
+        template::timings::{Timing, Timings},
+    };
+
+    #[test]
+    fn converts_days_to_dates() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(18_962), (2021, 12, 1));
+        assert_eq!(civil_from_days(19_722), (2023, 12, 31));
+    }
+
+    #[test]
+    fn formats_timestamps() {
+        let entry = HistoryEntry {
+            commit: None,
+            timestamp: 1_638_334_800,
+            timings: Timings::default(),
+        };
+        assert_eq!(entry.formatted_timestamp(), "2021-12-01 05:00 UTC");
+    }
+
+    #[test]
+    fn roundtrips_entries() {
+        let entry = HistoryEntry {
+            commit: Some("abc123".into()),
+            timestamp: 42,
+            timings: Timings {
+                data: vec![Timing {
+                    day: day!(15),
+                    parse: None,
+                    part_1: Some("1.0ms".into()),
+                    part_2: None,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse_heap: None,
+                    part_1_heap: None,
+                    part_2_heap: None,
+                    failed: vec![],
+                    aborted: None,
+                    peak_rss: None,
+                    total_nanos: 1_000_000.0,
+                }],
+            },
+        };
+        let line = JsonValue::from(&entry).stringify().unwrap();
+        let parsed = HistoryEntry::try_from(line.as_str()).unwrap();
+        assert_eq!(parsed.commit, entry.commit);
+        assert_eq!(parsed.timestamp, 42);
+        assert_eq!(parsed.timings.data[0].day, day!(15));
+    }
+
+    #[test]
+    fn resolves_commits() {
+        let git_dir = env::temp_dir().join(format!("aoc-history-{}", std::process::id()));
+        fs::create_dir_all(git_dir.join("refs/heads")).unwrap();
+
+        fs::write(git_dir.join("HEAD"), "0123abcd\n").unwrap();
+        assert_eq!(current_commit(&git_dir), Some("0123abcd".into()));
+
+        fs::write(git_dir.join("HEAD"), "ref: refs/heads/main\n").unwrap();
+        fs::write(
+            git_dir.join("packed-refs"),
+            "# pack-refs\nfeed1234 refs/heads/main\n",
+        )
+        .unwrap();
+        assert_eq!(current_commit(&git_dir), Some("feed1234".into()));
+
+        fs::write(git_dir.join("refs/heads/main"), "beef5678\n").unwrap();
+        assert_eq!(current_commit(&git_dir), Some("beef5678".into()));
+
+        fs::remove_dir_all(git_dir).unwrap();
+    }
+
+    #[test]
+    fn resolves_commits_of_worktrees() {
+        let root = env::temp_dir().join(format!("aoc-worktree-{}", std::process::id()));
+        let main_git_dir = root.join("main/.git");
+        let worktree_git_dir = main_git_dir.join("worktrees/feature");
+        fs::create_dir_all(main_git_dir.join("refs/heads")).unwrap();
+        fs::create_dir_all(&worktree_git_dir).unwrap();
+        fs::create_dir_all(root.join("feature")).unwrap();
+
+        fs::write(main_git_dir.join("refs/heads/feature"), "cafe9abc\n").unwrap();
+        fs::write(worktree_git_dir.join("HEAD"), "ref: refs/heads/feature\n").unwrap();
+        fs::write(worktree_git_dir.join("commondir"), "../..\n").unwrap();
+        fs::write(
+            root.join("feature/.git"),
+            "gitdir: ../main/.git/worktrees/feature\n",
+        )
+        .unwrap();
+
+        assert_eq!(git_dir(&main_git_dir), Some(main_git_dir.clone()));
+
+        let resolved = git_dir(&root.join("feature/.git")).unwrap();
+        assert_eq!(current_commit(&resolved), Some("cafe9abc".into()));
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,383 @@
+/// Append-only ledger of submitted answers and the verdicts they received.
+///
+/// Used to refuse submissions locally that are known to be wrong, since every wrong answer
+/// extends the time until the server accepts the next one.
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs::{self, OpenOptions},
+    io::{Error, Write},
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::{aoc_client::Verdict, paths, Day, Year};
+
+static LEDGER_FILE_NAME: &str = "submissions.jsonl";
+
+/// A single submitted answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LedgerEntry {
+    pub day: Day,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    /// Seconds since the unix epoch from which the server accepts the next answer, if it asked
+    /// to wait or locked out submissions after a wrong answer.
+    pub retry_at: Option<u64>,
+}
+
+/// Reason for refusing to submit an answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Refusal {
+    AlreadySolved(String),
+    AlreadyRejected(Verdict),
+    AboveBound(i128),
+    BelowBound(i128),
+    Wait(u64),
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::AlreadySolved(answer) => {
+                write!(f, "this part was already solved with answer `{answer}`.")
+            }
+            Self::AlreadyRejected(verdict) => {
+                write!(f, "this answer was already rejected ({verdict}).")
+            }
+            Self::AboveBound(bound) => {
+                write!(f, "{bound} was already too high, this answer is not lower.")
+            }
+            Self::BelowBound(bound) => {
+                write!(f, "{bound} was already too low, this answer is not higher.")
+            }
+            Self::Wait(seconds) => write!(f, "the server asked to wait another {seconds}s."),
+        }
+    }
+}
+
+/// All submissions recorded for the project.
+#[derive(Clone, Debug, Default)]
+pub struct Ledger {
+    pub entries: Vec<LedgerEntry>,
+}
+
+impl Ledger {
+    /// Read all entries from the ledger file, oldest first. Malformed lines are skipped.
+    #[must_use]
+    pub fn read_from_file(year: Year) -> Self {
+        let entries = fs::read_to_string(paths::data_file(year, LEDGER_FILE_NAME))
+            .map(|s| {
+                s.lines()
+                    .filter_map(|line| LedgerEntry::try_from(line).ok())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Self { entries }
+    }
+
+    /// Check whether an answer is worth submitting, given earlier verdicts for the same part.
+    ///
+    /// # Errors
+    ///
+    /// Will return the `Refusal` if the answer is known to be wrong or the server asked to wait.
+    pub fn check(&self, day: Day, part: u8, answer: &str, now: u64) -> Result<(), Refusal> {
+        let entries: Vec<&LedgerEntry> = self
+            .entries
+            .iter()
+            .filter(|e| e.day == day && e.part == part)
+            .collect();
+
+        if let Some(solved) = entries.iter().find(|e| e.verdict == Verdict::Correct) {
+            return Err(Refusal::AlreadySolved(solved.answer.clone()));
+        }
+
+        if let Some(rejected) = entries.iter().find(|e| {
+            e.answer == answer
+                && matches!(
+                    e.verdict,
+                    Verdict::TooHigh | Verdict::TooLow | Verdict::Incorrect
+                )
+        }) {
+            return Err(Refusal::AlreadyRejected(rejected.verdict.clone()));
+        }
+
+        if let Ok(value) = answer.parse::<i128>() {
+            let bound = |verdict: Verdict| {
+                entries
+                    .iter()
+                    .filter(move |e| e.verdict == verdict)
+                    .filter_map(|e| e.answer.parse::<i128>().ok())
+            };
+
+            if let Some(high) = bound(Verdict::TooHigh).min().filter(|&high| value >= high) {
+                return Err(Refusal::AboveBound(high));
+            }
+
+            if let Some(low) = bound(Verdict::TooLow).max().filter(|&low| value <= low) {
+                return Err(Refusal::BelowBound(low));
+            }
+        }
+
+        if let Some(until) = entries.last().and_then(|e| e.retry_at) {
+            if until > now {
+                return Err(Refusal::Wait(until - now));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl LedgerEntry {
+    /// Create an entry for a submission made just now, after which the server asked to wait
+    /// `wait_seconds` before the next one.
+    #[must_use]
+    pub fn now(
+        day: Day,
+        part: u8,
+        answer: &str,
+        verdict: Verdict,
+        wait_seconds: Option<u64>,
+    ) -> Self {
+        let timestamp = now();
+        Self {
+            day,
+            part,
+            answer: answer.to_string(),
+            verdict,
+            timestamp,
+            retry_at: wait_seconds.map(|seconds| timestamp + seconds),
+        }
+    }
+
+    /// Append the entry as a single JSON line to the ledger file.
+    pub fn append_to_file(&self, year: Year) -> Result<(), Error> {
+        let line = JsonValue::from(self)
+            .stringify()
+            .map_err(|e| Error::other(e.message().to_string()))?;
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(paths::data_file(year, LEDGER_FILE_NAME))?;
+
+        writeln!(file, "{line}")
+    }
+}
+
+/// Seconds since the unix epoch.
+#[must_use]
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&LedgerEntry> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &LedgerEntry) -> Self {
+        let mut map: HashMap<String, Self> = HashMap::new();
+
+        map.insert("day".into(), Self::String(value.day.to_string()));
+        map.insert("part".into(), Self::Number(f64::from(value.part)));
+        map.insert("answer".into(), Self::String(value.answer.clone()));
+        map.insert("verdict".into(), Self::String(value.verdict.to_string()));
+
+        let wait_seconds = match value.verdict {
+            Verdict::Wait(Some(seconds)) => Self::Number(seconds as f64),
+            _ => Self::Null,
+        };
+        map.insert("wait_seconds".into(), wait_seconds);
+        map.insert("timestamp".into(), Self::Number(value.timestamp as f64));
+        map.insert(
+            "retry_at".into(),
+            value
+                .retry_at
+                .map_or(Self::Null, |retry_at| Self::Number(retry_at as f64)),
+        );
+
+        Self::Object(map)
+    }
+}
+
+impl TryFrom<&str> for LedgerEntry {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(value).or(Err("not valid JSON."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected ledger entry to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected entry.day to be a Day struct.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .ok_or("Expected entry.part to be a number.")?;
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected entry.answer to be a string.")?;
+
+        let mut verdict = json
+            .get("verdict")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected entry.verdict to be a string.")?
+            .parse::<Verdict>()?;
+
+        if let Verdict::Wait(seconds) = &mut verdict {
+            *seconds = json
+                .get("wait_seconds")
+                .and_then(|v| v.get::<f64>())
+                .map(|s| *s as u64);
+        }
+
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .ok_or("Expected entry.timestamp to be a number.")?;
+
+        let timestamp = *timestamp as u64;
+
+        // entries written before `retry_at` was stored only kept the wait of a `wait` verdict.
+        let retry_at = json
+            .get("retry_at")
+            .and_then(|v| v.get::<f64>())
+            .map(|retry_at| *retry_at as u64)
+            .or_else(|| match verdict {
+                Verdict::Wait(Some(seconds)) => Some(timestamp + seconds),
+                _ => None,
+            });
+
+        Ok(Self {
+            day,
+            part: *part as u8,
+            answer: answer.clone(),
+            verdict,
+            timestamp,
+            retry_at,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::{Ledger, LedgerEntry, Refusal};
+    use crate::{day, template::aoc_client::Verdict};
+
+    fn entry(part: u8, answer: &str, verdict: Verdict, timestamp: u64) -> LedgerEntry {
+        let retry_at = match verdict {
+            Verdict::Wait(Some(seconds)) => Some(timestamp + seconds),
+            _ => None,
+        };
+
+        LedgerEntry {
+            day: day!(1),
+            part,
+            answer: answer.into(),
+            verdict,
+            timestamp,
+            retry_at,
+        }
+    }
+
+    #[test]
+    fn allows_first_submission() {
+        assert_eq!(Ledger::default().check(day!(1), 1, "42", 0), Ok(()));
+    }
+
+    #[test]
+    fn refuses_known_bad_answers() {
+        let ledger = Ledger {
+            entries: vec![
+                entry(1, "100", Verdict::TooHigh, 0),
+                entry(1, "10", Verdict::TooLow, 0),
+                entry(1, "abc", Verdict::Incorrect, 0),
+            ],
+        };
+
+        assert_eq!(
+            ledger.check(day!(1), 1, "abc", 0),
+            Err(Refusal::AlreadyRejected(Verdict::Incorrect))
+        );
+        assert_eq!(
+            ledger.check(day!(1), 1, "100", 0),
+            Err(Refusal::AlreadyRejected(Verdict::TooHigh))
+        );
+        assert_eq!(
+            ledger.check(day!(1), 1, "150", 0),
+            Err(Refusal::AboveBound(100))
+        );
+        assert_eq!(
+            ledger.check(day!(1), 1, "-3", 0),
+            Err(Refusal::BelowBound(10))
+        );
+        assert_eq!(ledger.check(day!(1), 1, "50", 0), Ok(()));
+        assert_eq!(ledger.check(day!(1), 2, "150", 0), Ok(()));
+        assert_eq!(ledger.check(day!(2), 1, "abc", 0), Ok(()));
+    }
+
+    #[test]
+    fn refuses_solved_parts() {
+        let ledger = Ledger {
+            entries: vec![entry(2, "7", Verdict::Correct, 0)],
+        };
+        assert_eq!(
+            ledger.check(day!(1), 2, "8", 0),
+            Err(Refusal::AlreadySolved("7".into()))
+        );
+    }
+
+    #[test]
+    fn honors_wait_times() {
+        let ledger = Ledger {
+            entries: vec![entry(1, "5", Verdict::Wait(Some(60)), 1000)],
+        };
+        assert_eq!(ledger.check(day!(1), 1, "5", 1030), Err(Refusal::Wait(30)));
+        assert_eq!(ledger.check(day!(1), 1, "5", 1060), Ok(()));
+    }
+
+    #[test]
+    fn honors_lockouts_after_wrong_answers() {
+        let mut wrong = entry(1, "5", Verdict::Incorrect, 1000);
+        wrong.retry_at = Some(1300);
+        let ledger = Ledger {
+            entries: vec![wrong],
+        };
+
+        assert_eq!(ledger.check(day!(1), 1, "6", 1100), Err(Refusal::Wait(200)));
+        assert_eq!(ledger.check(day!(1), 1, "6", 1300), Ok(()));
+    }
+
+    #[test]
+    fn roundtrips_entries() {
+        let original = entry(2, "multi\nline", Verdict::Wait(Some(37)), 42);
+        let line = JsonValue::from(&original).stringify().unwrap();
+        assert_eq!(LedgerEntry::try_from(line.as_str()).unwrap(), original);
+
+        let mut original = entry(1, "12", Verdict::TooLow, 43);
+        original.retry_at = Some(103);
+        let line = JsonValue::from(&original).stringify().unwrap();
+        assert_eq!(LedgerEntry::try_from(line.as_str()).unwrap(), original);
+    }
+}
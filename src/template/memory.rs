@@ -0,0 +1,194 @@
+/// Measurement of the memory usage of solution runs.
+use std::{collections::HashMap, fs};
+use tinyjson::JsonValue;
+
+/// Heap usage of a single part, as measured by `dhat` in `cargo time --heap` runs.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct HeapStats {
+    /// Number of allocations made by the part.
+    pub allocations: u64,
+    /// Total number of bytes allocated by the part.
+    pub bytes: u64,
+    /// Number of bytes allocated at the peak of the part.
+    pub peak_bytes: u64,
+}
+
+impl HeapStats {
+    /// Combine the stats of several parts, e.g. into the stats of a day.
+    #[must_use]
+    pub fn combine(stats: impl IntoIterator<Item = Self>) -> Option<Self> {
+        stats.into_iter().reduce(|a, b| Self {
+            allocations: a.allocations + b.allocations,
+            bytes: a.bytes + b.bytes,
+            peak_bytes: a.peak_bytes.max(b.peak_bytes),
+        })
+    }
+}
+
+/// Peak resident set size of the current process in bytes, if it can be determined.
+///
+/// Reads `VmHWM` from `/proc/self/status` on Linux and falls back to `getrusage` elsewhere.
+#[must_use]
+pub fn peak_rss() -> Option<u64> {
+    fs::read_to_string("/proc/self/status")
+        .ok()
+        .and_then(|status| parse_vm_hwm(&status))
+        .or_else(rusage_max_rss)
+}
+
+/// Format a number of bytes with a binary unit, e.g. `12.3 MiB`.
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+
+    let mut value = bytes as f64;
+    let mut unit = 0;
+
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
+fn parse_vm_hwm(status: &str) -> Option<u64> {
+    let kib: u64 = status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmHWM:"))?
+        .trim()
+        .strip_suffix("kB")?
+        .trim()
+        .parse()
+        .ok()?;
+
+    Some(kib * 1024)
+}
+
+#[cfg(unix)]
+fn rusage_max_rss() -> Option<u64> {
+    let mut usage = std::mem::MaybeUninit::<libc::rusage>::uninit();
+
+    // SAFETY: `getrusage` initializes the passed struct if it returns 0.
+    let usage = unsafe {
+        if libc::getrusage(libc::RUSAGE_SELF, usage.as_mut_ptr()) != 0 {
+            return None;
+        }
+        usage.assume_init()
+    };
+
+    let max_rss = u64::try_from(usage.ru_maxrss).ok()?;
+
+    // `ru_maxrss` is reported in bytes on macOS and in kilobytes elsewhere.
+    if cfg!(target_os = "macos") {
+        Some(max_rss)
+    } else {
+        Some(max_rss * 1024)
+    }
+}
+
+#[cfg(not(unix))]
+const fn rusage_max_rss() -> Option<u64> {
+    None
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&HeapStats> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &HeapStats) -> Self {
+        let mut map: HashMap<String, Self> = HashMap::new();
+
+        map.insert("allocations".into(), Self::Number(value.allocations as f64));
+        map.insert("bytes".into(), Self::Number(value.bytes as f64));
+        map.insert("peak_bytes".into(), Self::Number(value.peak_bytes as f64));
+
+        Self::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for HeapStats {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected heap stats to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .map(|v| v as u64)
+                .ok_or_else(|| format!("Expected heap.{key} to be a number."))
+        };
+
+        Ok(Self {
+            allocations: number("allocations")?,
+            bytes: number("bytes")?,
+            peak_bytes: number("peak_bytes")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::{format_bytes, parse_vm_hwm, peak_rss, HeapStats};
+
+    #[test]
+    fn parses_proc_status() {
+        let status = "Name:\tadvent_of_code\nVmPeak:\t   12000 kB\nVmHWM:\t    2048 kB\nVmRSS:\t    1024 kB\n";
+        assert_eq!(parse_vm_hwm(status), Some(2 * 1024 * 1024));
+        assert_eq!(parse_vm_hwm("Name:\tadvent_of_code\n"), None);
+    }
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(2048), "2.0 KiB");
+        assert_eq!(format_bytes(12_897_485), "12.3 MiB");
+        assert_eq!(format_bytes(3 << 30), "3.0 GiB");
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn measures_current_process() {
+        assert!(peak_rss().is_some_and(|bytes| bytes > 0));
+    }
+
+    #[test]
+    fn combines_heap_stats() {
+        let part = |allocations, bytes, peak_bytes| HeapStats {
+            allocations,
+            bytes,
+            peak_bytes,
+        };
+        assert_eq!(
+            HeapStats::combine([part(3, 100, 60), part(2, 50, 80)]),
+            Some(part(5, 150, 80))
+        );
+        assert_eq!(HeapStats::combine([]), None);
+    }
+
+    #[test]
+    fn roundtrips_heap_stats() {
+        let stats = HeapStats {
+            allocations: 12,
+            bytes: 4096,
+            peak_bytes: 1024,
+        };
+        assert_eq!(
+            HeapStats::try_from(&JsonValue::from(&stats)).unwrap(),
+            stats
+        );
+    }
+}
@@ -1,15 +1,34 @@
-use std::{env, fs};
+use std::{fs, io, path::PathBuf};
 
-pub mod aoc_cli;
+pub mod aoc_client;
 pub mod commands;
+pub mod config;
+pub mod registry;
 pub mod runner;
 
 pub use day::*;
+pub use parse_error::ParseError;
+pub use record::{OutputFormat, PARSE_PART};
+pub use run_multi::Limits;
+pub use year::*;
 
+mod answers;
+mod compare;
+mod crypt;
 mod day;
+mod examples;
+mod history;
+mod ledger;
+mod memory;
+mod parse_error;
+mod paths;
 mod readme_benchmarks;
+mod record;
+mod report;
 mod run_multi;
+mod stats;
 mod timings;
+mod year;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
@@ -21,56 +40,173 @@ pub const ANSI_RESET: &str = "\x1b[0m";
 ///
 /// Will panic if the user has insufficient permissions to access
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+pub fn read_file(folder: &str, year: Year, day: Day) -> String {
+    try_read_file(folder, year, day).expect("could not open input file")
+}
+
+/// Helper function that reads a text file to a string, returning an error instead of panicking.
+///
+/// # Errors
+///
+/// Will return an error if the file does not exist or cannot be read.
+pub fn try_read_file(folder: &str, year: Year, day: Day) -> io::Result<String> {
+    let filepath = PathBuf::from(paths::day_file(folder, year, day));
+
+    // fall back to the encrypted blob of the file, if the repository holds one.
+    // an empty plain file is a placeholder that must not hide the blob.
+    let text = match fs::read_to_string(&filepath) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+        Ok(text) if !text.is_empty() => Ok(text),
+        _ if crypt::encrypted_path(&filepath).exists() => {
+            crypt::read_encrypted(&filepath).map_err(io::Error::other)
+        }
+        result => result,
+    }?;
+
+    Ok(strip_example_header(folder, text))
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 ///
 /// # Panics
 ///
-/// Will panic if the user has insufficient permissions to access the current directory.
+/// Will panic if the user has insufficient permissions to access the file.
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
+pub fn read_file_part(folder: &str, year: Year, day: Day, part: u8) -> String {
+    let filepath = paths::day_part_file(folder, year, day, part);
     let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    strip_example_header(folder, f.expect("could not open input file"))
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Example files may declare their expected answers in a header, which is not part of the input.
+fn strip_example_header(folder: &str, text: String) -> String {
+    if folder == "examples" {
+        examples::strip_header(text)
+    } else {
+        text
+    }
+}
+
+/// Creates the constants `YEAR` and `DAY` and sets up the input and runner for each part.
+///
+/// The optional, third parameter (1 or 2) allows you to only run a single part of the solution.
 ///
-/// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+/// Parts return an `Option` of the answer, or a `Result` whose error is reported and marks the
+/// part as failed. A [`ParseError`] also reports the line and column the error occurred at.
+///
+/// An optional `parse = <fn>` hook parses the input once, before the parts run. It receives the
+/// input as `&str` and returns an `Option` or `Result` of the parsed value, which is passed to
+/// each part by reference. Its runtime is reported separately as the "Parse" phase.
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+    ($year:expr, $day:expr $(, parse = $parse:expr)?) => {
+        $crate::solution!(@impl $year, $day, [$($parse)?], [part_one, 1] [part_two, 2]);
     };
-    ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+    ($year:expr, $day:expr, 1 $(, parse = $parse:expr)?) => {
+        $crate::solution!(@impl $year, $day, [$($parse)?], [part_one, 1]);
     };
-    ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+    ($year:expr, $day:expr, 2 $(, parse = $parse:expr)?) => {
+        $crate::solution!(@impl $year, $day, [$($parse)?], [part_two, 2]);
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    (@impl $year:expr, $day:expr, [], $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@consts $year, $day);
+
+        fn main() {
+            use $crate::template::runner::*;
+            apply_memory_limit();
+            let input = read_input(YEAR, DAY);
+            $( run_part($func, &input, YEAR, DAY, $part); )*
+        }
+
+        /// Registry entry that allows running this day in-process.
+        pub const SOLUTION: $crate::template::registry::Solution =
+            $crate::template::registry::Solution {
+                year: YEAR,
+                day: DAY,
+                parts: &[$(
+                    $crate::template::registry::SolutionPart {
+                        part: $part,
+                        run: |input, is_timed| {
+                            $crate::template::runner::solve_part($func, input, DAY, $part, is_timed)
+                        },
+                    },
+                )*],
+            };
+    };
+
+    (@impl $year:expr, $day:expr, [$parse:expr], $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@consts $year, $day);
+
+        fn main() {
+            use $crate::template::runner::*;
+            apply_memory_limit();
+            let input = read_input(YEAR, DAY);
+            match run_parse($parse, &input, DAY) {
+                Some(parsed) => { $( run_part($func, &parsed, YEAR, DAY, $part); )* }
+                None => { $( skip_part(DAY, $part); )* }
+            }
+        }
+
+        /// Registry entry that allows running this day in-process.
+        /// Every part parses the input again, untimed, so that parts can run in isolation.
+        pub const SOLUTION: $crate::template::registry::Solution =
+            $crate::template::registry::Solution {
+                year: YEAR,
+                day: DAY,
+                parts: &[
+                    $crate::template::registry::SolutionPart {
+                        part: $crate::template::PARSE_PART,
+                        run: |input, is_timed| {
+                            $crate::template::runner::solve_parse($parse, input, DAY, is_timed)
+                        },
+                    },
+                    $(
+                        $crate::template::registry::SolutionPart {
+                            part: $part,
+                            run: |input, is_timed| match $crate::template::runner::PartOutput::into_value($parse(input)) {
+                                Some(parsed) => $crate::template::runner::solve_part(
+                                    $func, &parsed, DAY, $part, is_timed,
+                                ),
+                                None => $crate::template::runner::unparsed_record(DAY, $part),
+                            },
+                        },
+                    )*
+                ],
+            };
+    };
+
+    (@consts $year:expr, $day:expr) => {
+        /// The event year.
+        const YEAR: $crate::template::Year = $crate::year!($year);
+
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
         #[cfg(feature = "dhat-heap")]
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
+    };
+}
 
-        fn main() {
-            use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+/// Generates one test per example file and part of the current day, checking the answers
+/// declared in the header of each example file.
+///
+/// Example files are discovered at build time: `data/{year}/examples/{day}.txt` and any
+/// `data/{year}/examples/{day}-{name}.txt`, in the `paths.examples` folder of `aoc.toml`. Parts
+/// that are not declared are not tested, so adding an example is a data-only change. Parts
+/// declared without an answer fail until it is filled in.
+#[macro_export]
+macro_rules! example_tests {
+    () => {
+        #[cfg(test)]
+        mod example_tests {
+            include!(concat!(
+                env!("OUT_DIR"),
+                "/example_tests/",
+                env!("CARGO_BIN_NAME"),
+                ".rs"
+            ));
         }
     };
 }
@@ -0,0 +1,80 @@
+use std::error::Error;
+use std::fmt::Display;
+
+/// An error returned by solutions that fail to parse their input.
+///
+/// # Display
+/// The error displays its message, prefixed with the position in the input if known.
+///
+/// ```
+/// # use advent_of_code::template::ParseError;
+/// let error = ParseError::new("expected a digit").at(3, 7);
+/// assert_eq!(error.to_string(), "line 3, column 7: expected a digit")
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub message: String,
+    /// One-based line of the input the error occurred on.
+    pub line: Option<usize>,
+    /// One-based column of the input the error occurred on.
+    pub column: Option<usize>,
+}
+
+impl ParseError {
+    #[must_use]
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            line: None,
+            column: None,
+        }
+    }
+
+    /// Attach the line the error occurred on.
+    #[must_use]
+    pub const fn at_line(mut self, line: usize) -> Self {
+        self.line = Some(line);
+        self
+    }
+
+    /// Attach the line and column the error occurred at.
+    #[must_use]
+    pub const fn at(mut self, line: usize, column: usize) -> Self {
+        self.line = Some(line);
+        self.column = Some(column);
+        self
+    }
+}
+
+impl Error for ParseError {}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => write!(f, "line {line}, column {column}: "),
+            (Some(line), None) => write!(f, "line {line}: "),
+            _ => Ok(()),
+        }?;
+        f.write_str(&self.message)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::ParseError;
+
+    #[test]
+    fn displays_positions() {
+        assert_eq!(ParseError::new("empty input").to_string(), "empty input");
+        assert_eq!(
+            ParseError::new("bad vent").at_line(4).to_string(),
+            "line 4: bad vent"
+        );
+        assert_eq!(
+            ParseError::new("expected `,`").at(2, 9).to_string(),
+            "line 2, column 9: expected `,`"
+        );
+    }
+}
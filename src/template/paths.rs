@@ -0,0 +1,100 @@
+/// Locations of the files belonging to a year and day.
+///
+/// Data lives in `data/{year}/` and solutions in `src/bin/{year}-{day}.rs`, so that several
+/// events can share one workspace. The data directories can be moved in `aoc.toml`.
+///
+/// All paths are resolved against the project root rather than the working directory, so
+/// commands behave the same when run from a subdirectory or on another checkout.
+use std::{
+    env,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+use crate::template::{config, Day, Year};
+
+/// Env var that sets the project root, e.g. to run the commands on another checkout.
+pub const ROOT_ENV: &str = "AOC_ROOT";
+
+static ROOT: OnceLock<PathBuf> = OnceLock::new();
+
+/// The project root: `AOC_ROOT` if set, otherwise the closest directory at or above the working
+/// directory that holds a `Cargo.toml`, falling back to the working directory itself.
+pub fn root() -> &'static Path {
+    ROOT.get_or_init(|| {
+        let cwd = env::current_dir().unwrap_or_default();
+
+        env::var_os(ROOT_ENV).map_or_else(
+            || {
+                cwd.ancestors()
+                    .find(|dir| dir.join("Cargo.toml").is_file())
+                    .map_or_else(|| cwd.clone(), Path::to_path_buf)
+            },
+            |root| cwd.join(root),
+        )
+    })
+}
+
+/// Resolve a path relative to the project root. Absolute paths are returned unchanged.
+pub fn resolve(path: impl AsRef<Path>) -> PathBuf {
+    root().join(path)
+}
+
+/// Directory holding the data files of a year, e.g. `data/2021`.
+#[must_use]
+pub fn data_dir(year: Year) -> String {
+    resolve_str(&config::get().paths.data_dir(year))
+}
+
+/// Path of a file in the data directory of a year, e.g. `data/2021/timings.json`.
+#[must_use]
+pub fn data_file(year: Year, file_name: &str) -> String {
+    format!("{}/{file_name}", data_dir(year))
+}
+
+/// Directory of a data folder of a year, e.g. `data/2021/examples`.
+#[must_use]
+pub fn folder_dir(folder: &str, year: Year) -> String {
+    resolve_str(&config::get().paths.folder(folder, year))
+}
+
+/// Path of a day's text file in a data folder, e.g. `data/2021/inputs/01.txt`.
+#[must_use]
+pub fn day_file(folder: &str, year: Year, day: Day) -> String {
+    format!("{}/{day}.txt", folder_dir(folder, year))
+}
+
+/// Path of a day's text file for a single part, e.g. `data/2021/examples/01-2.txt`.
+#[must_use]
+pub fn day_part_file(folder: &str, year: Year, day: Day, part: u8) -> String {
+    format!("{}/{day}-{part}.txt", folder_dir(folder, year))
+}
+
+/// Path of the puzzle description of a day, e.g. `data/2021/puzzles/01.md`.
+#[must_use]
+pub fn puzzle_file(year: Year, day: Day) -> String {
+    format!("{}/{day}.md", folder_dir("puzzles", year))
+}
+
+/// Name of the binary holding the solution for a day, e.g. `2021-01`.
+#[must_use]
+pub fn bin_name(year: Year, day: Day) -> String {
+    format!("{year}-{day}")
+}
+
+/// Path of the source file holding the solution for a day, e.g. `src/bin/2021-01.rs`.
+#[must_use]
+pub fn bin_file(year: Year, day: Day) -> String {
+    resolve_str(&format!("src/bin/{}.rs", bin_name(year, day)))
+}
+
+/// Link to the solution of a day from the project root, e.g. `./src/bin/2021-01.rs`.
+#[must_use]
+pub fn bin_link(year: Year, day: Day) -> String {
+    format!("./src/bin/{}.rs", bin_name(year, day))
+}
+
+/// Resolve a path of the project, keeping it a string for use in messages.
+fn resolve_str(path: &str) -> String {
+    resolve(path).to_string_lossy().into_owned()
+}
@@ -2,12 +2,10 @@
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
 use std::{fs, io};
 
+use crate::template::config::{self, ReadmeConfig};
 use crate::template::timings::Timings;
-use crate::template::Day;
+use crate::template::{memory, paths, Year, PARSE_PART};
 
-static MARKER: &str = "<!--- benchmarking table --->";
-
-#[allow(dead_code)]
 #[derive(Debug)]
 pub enum Error {
     Parser(String),
@@ -25,17 +23,12 @@ pub struct TablePosition {
     pos_end: usize,
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
         return Err(Error::Parser(
-            "{}: too many occurences of marker in README.".into(),
+            "Too many occurences of marker in README.".into(),
         ));
     }
 
@@ -52,75 +45,177 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
+fn construct_table(
+    marker: &str,
+    prefix: &str,
+    year: Year,
+    timings: Timings,
+    total_millis: f64,
+) -> String {
     let header = format!("{prefix} Benchmarks");
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+    // only show the parse phase if a solution declares a parse hook.
+    let has_parse = timings.data.iter().any(|t| t.parse.is_some());
+    // only show memory if it was measured, which timings stored by older versions lack.
+    let has_memory = timings.data.iter().any(|t| t.peak_rss.is_some());
+    let has_heap = timings.data.iter().any(|t| t.heap().is_some());
+
+    let mut lines: Vec<String> = vec![marker.into(), header, String::new()];
+
+    let mut columns = vec!["Day"];
+    if has_parse {
+        columns.push("Parse");
+    }
+    columns.extend(["Part 1", "Part 2"]);
+    if has_memory {
+        columns.push("Memory");
+    }
+    if has_heap {
+        columns.extend(["Allocations", "Allocated", "Peak heap"]);
+    }
+
+    lines.push(format!("| {} |", columns.join(" | ")));
+    lines.push(format!("| {}  |", vec![":---:"; columns.len()].join(" | ")));
 
     for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+        let path = paths::bin_link(year, timing.day);
+        let cell = |part: u8, time: Option<&String>| match time {
+            Some(time) => format!("`{time}`"),
+            None if timing.is_failed(part) => "`failed`".into(),
+            // parts that did not finish before the day was stopped.
+            None => timing
+                .aborted
+                .map_or_else(|| "`-`".into(), |abort| format!("`{abort}`")),
+        };
+
+        let mut cells = vec![format!("[Day {}]({})", timing.day.into_inner(), path)];
+        if has_parse {
+            cells.push(cell(PARSE_PART, timing.parse.as_ref()));
+        }
+        cells.push(cell(1, timing.part_1.as_ref()));
+        cells.push(cell(2, timing.part_2.as_ref()));
+        if has_memory {
+            cells.push(timing.peak_rss.map_or_else(
+                || "`-`".into(),
+                |bytes| format!("`{}`", memory::format_bytes(bytes)),
+            ));
+        }
+        if has_heap {
+            match timing.heap() {
+                Some(heap) => cells.extend([
+                    format!("`{}`", heap.allocations),
+                    format!("`{}`", memory::format_bytes(heap.bytes)),
+                    format!("`{}`", memory::format_bytes(heap.peak_bytes)),
+                ]),
+                None => cells.extend(["`-`".into(), "`-`".into(), "`-`".into()]),
+            }
+        }
+
+        lines.push(format!("| {} |", cells.join(" | ")));
     }
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
+    lines.push(marker.into());
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+/// Replace the table of `year` if the README has one, or the shared table otherwise.
+fn update_content(
+    s: &mut String,
+    readme: &ReadmeConfig,
+    year: Year,
+    timings: Timings,
+    total_millis: f64,
+) -> Result<(), Error> {
+    // the year marker is meant for READMEs that show several years.
+    let year_marker = readme.year_marker(year);
+    let (marker, prefix) = if s.contains(&year_marker) {
+        (year_marker.as_str(), format!("## {year}"))
+    } else {
+        (readme.marker.as_str(), "##".into())
+    };
+
+    let positions = locate_table(s, marker)?;
+    let table = construct_table(marker, &prefix, year, timings, total_millis);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
-pub fn update(timings: Timings) -> Result<(), Error> {
-    let path = "README.md";
-    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+pub fn update(year: Year, timings: Timings) -> Result<(), Error> {
+    let config = &config::get().readme;
+    let path = paths::resolve(&config.path);
+    let mut readme = String::from_utf8_lossy(&fs::read(&path)?).to_string();
     let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
-    fs::write(path, &readme)?;
+    update_content(&mut readme, config, year, timings, total_millis)?;
+    fs::write(&path, &readme)?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use super::update_content;
+    use crate::{
+        day,
+        template::config::ReadmeConfig,
+        template::memory::HeapStats,
+        template::record::Abort,
+        template::timings::{Timing, Timings},
+        year,
+    };
+
+    static MARKER: &str = "<!--- benchmarking table --->";
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
                     day: day!(1),
+                    parse: None,
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse_heap: None,
+                    part_1_heap: None,
+                    part_2_heap: None,
+                    failed: vec![],
+                    aborted: None,
+                    peak_rss: None,
                     total_nanos: 3e+10,
                 },
                 Timing {
                     day: day!(2),
+                    parse: None,
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse_heap: None,
+                    part_1_heap: None,
+                    part_2_heap: None,
+                    failed: vec![],
+                    aborted: None,
+                    peak_rss: None,
                     total_nanos: 7e+10,
                 },
                 Timing {
                     day: day!(4),
+                    parse: None,
                     part_1: Some("40ms".into()),
                     part_2: Some("50ms".into()),
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse_heap: None,
+                    part_1_heap: None,
+                    part_2_heap: None,
+                    failed: vec![],
+                    aborted: None,
+                    peak_rss: None,
                     total_nanos: 9e+10,
                 },
             ],
@@ -131,28 +226,63 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            &ReadmeConfig::default(),
+            year!(2021),
+            get_mock_timings(),
+            190.0,
+        )
+        .unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            &ReadmeConfig::default(),
+            year!(2021),
+            get_mock_timings(),
+            190.0,
+        )
+        .unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            &ReadmeConfig::default(),
+            year!(2021),
+            get_mock_timings(),
+            190.0,
+        )
+        .unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            &ReadmeConfig::default(),
+            year!(2021),
+            get_mock_timings(),
+            190.0,
+        )
+        .unwrap();
+        update_content(
+            &mut s,
+            &ReadmeConfig::default(),
+            year!(2021),
+            get_mock_timings(),
+            190.0,
+        )
+        .unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -160,7 +290,14 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            &ReadmeConfig::default(),
+            year!(2021),
+            get_mock_timings(),
+            190.0,
+        )
+        .unwrap();
         let expected = [
             "foo",
             "bar",
@@ -169,9 +306,9 @@ mod tests {
             "",
             "| Day | Part 1 | Part 2 |",
             "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| [Day 1](./src/bin/2021-01.rs) | `10ms` | `20ms` |",
+            "| [Day 2](./src/bin/2021-02.rs) | `30ms` | `40ms` |",
+            "| [Day 4](./src/bin/2021-04.rs) | `40ms` | `50ms` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -180,4 +317,104 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn prefers_year_specific_tables() {
+        let year_marker = "<!--- benchmarking table 2021 --->";
+        let mut s = format!("{MARKER}{MARKER}\n{year_marker}{year_marker}");
+        update_content(
+            &mut s,
+            &ReadmeConfig::default(),
+            year!(2021),
+            get_mock_timings(),
+            190.0,
+        )
+        .unwrap();
+        assert_eq!(s.matches("## 2021 Benchmarks").count(), 1);
+        assert!(s.starts_with(&format!("{MARKER}{MARKER}\n")));
+    }
+
+    #[test]
+    fn shows_parse_column_if_present() {
+        let mut timings = get_mock_timings();
+        timings.data[1].parse = Some("5ms".into());
+
+        let mut s = format!("{MARKER}{MARKER}");
+        update_content(
+            &mut s,
+            &ReadmeConfig::default(),
+            year!(2021),
+            timings,
+            190.0,
+        )
+        .unwrap();
+        assert!(s.contains("| Day | Parse | Part 1 | Part 2 |"));
+        assert!(s.contains("| [Day 1](./src/bin/2021-01.rs) | `-` | `10ms` | `20ms` |"));
+        assert!(s.contains("| [Day 2](./src/bin/2021-02.rs) | `5ms` | `30ms` | `40ms` |"));
+    }
+
+    #[test]
+    fn shows_aborted_parts() {
+        let mut timings = get_mock_timings();
+        timings.data[2].part_2 = None;
+        timings.data[2].aborted = Some(Abort::Timeout);
+
+        let mut s = format!("{MARKER}{MARKER}");
+        update_content(
+            &mut s,
+            &ReadmeConfig::default(),
+            year!(2021),
+            timings,
+            190.0,
+        )
+        .unwrap();
+        assert!(s.contains("| [Day 4](./src/bin/2021-04.rs) | `40ms` | `TIMEOUT` |"));
+    }
+
+    #[test]
+    fn shows_memory_column_if_measured() {
+        let mut timings = get_mock_timings();
+        timings.data[0].peak_rss = Some(12_897_485);
+
+        let mut s = format!("{MARKER}{MARKER}");
+        update_content(
+            &mut s,
+            &ReadmeConfig::default(),
+            year!(2021),
+            timings,
+            190.0,
+        )
+        .unwrap();
+        assert!(s.contains("| Day | Part 1 | Part 2 | Memory |"));
+        assert!(s.contains("| :---: | :---: | :---: | :---:  |"));
+        assert!(s.contains("| [Day 1](./src/bin/2021-01.rs) | `10ms` | `20ms` | `12.3 MiB` |"));
+        assert!(s.contains("| [Day 2](./src/bin/2021-02.rs) | `30ms` | `40ms` | `-` |"));
+    }
+
+    #[test]
+    fn shows_heap_columns_if_profiled() {
+        let mut timings = get_mock_timings();
+        let heap = |allocations, bytes, peak_bytes| HeapStats {
+            allocations,
+            bytes,
+            peak_bytes,
+        };
+        timings.data[0].part_1_heap = Some(heap(3, 2048, 1024));
+        timings.data[0].part_2_heap = Some(heap(2, 1024, 1536));
+
+        let mut s = format!("{MARKER}{MARKER}");
+        update_content(
+            &mut s,
+            &ReadmeConfig::default(),
+            year!(2021),
+            timings,
+            190.0,
+        )
+        .unwrap();
+        assert!(s.contains("| Day | Part 1 | Part 2 | Allocations | Allocated | Peak heap |"));
+        assert!(s.contains(
+            "| [Day 1](./src/bin/2021-01.rs) | `10ms` | `20ms` | `5` | `3.0 KiB` | `1.5 KiB` |"
+        ));
+        assert!(s.contains("| [Day 2](./src/bin/2021-02.rs) | `30ms` | `40ms` | `-` | `-` | `-` |"));
+    }
 }
@@ -0,0 +1,393 @@
+/// Machine-readable records of solution runs.
+use std::{collections::HashMap, fmt::Display, str::FromStr, time::Duration};
+use tinyjson::JsonValue;
+
+use crate::template::{memory::HeapStats, stats::BenchStats, Day};
+
+/// The output format used when printing results of solution runs.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Human-readable text, including ANSI codes.
+    #[default]
+    Text,
+    /// One JSON record per line and part.
+    Json,
+}
+
+impl OutputFormat {
+    #[must_use]
+    pub const fn is_json(self) -> bool {
+        matches!(self, Self::Json)
+    }
+}
+
+impl Display for OutputFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Text => f.write_str("text"),
+            Self::Json => f.write_str("json"),
+        }
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = OutputFormatFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            _ => Err(OutputFormatFromStrError),
+        }
+    }
+}
+
+/// An error which can be returned when parsing an [`OutputFormat`].
+#[derive(Debug)]
+pub struct OutputFormatFromStrError;
+
+impl std::error::Error for OutputFormatFromStrError {}
+
+impl Display for OutputFormatFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting an output format of `text` or `json`")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The outcome of running a single part of a solution.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PartStatus {
+    Solved,
+    /// The part returned no answer.
+    Unsolved,
+    /// The part returned an error.
+    Failed,
+    /// The part did not finish before its day was stopped by the timeout.
+    Timeout,
+    /// The part did not finish before its day exceeded the memory limit.
+    OutOfMemory,
+}
+
+impl Display for PartStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Solved => f.write_str("solved"),
+            Self::Unsolved => f.write_str("unsolved"),
+            Self::Failed => f.write_str("failed"),
+            Self::Timeout => f.write_str("timeout"),
+            Self::OutOfMemory => f.write_str("oom"),
+        }
+    }
+}
+
+impl FromStr for PartStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "solved" => Ok(Self::Solved),
+            "unsolved" => Ok(Self::Unsolved),
+            "failed" => Ok(Self::Failed),
+            "timeout" => Ok(Self::Timeout),
+            "oom" => Ok(Self::OutOfMemory),
+            _ => Err(format!("unknown part status `{s}`.")),
+        }
+    }
+}
+
+/// Why a day was stopped before all of its parts finished.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Abort {
+    /// The day ran longer than the configured wall-clock timeout.
+    Timeout,
+    /// The day exceeded the configured address-space limit.
+    OutOfMemory,
+}
+
+impl Display for Abort {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Timeout => f.write_str("TIMEOUT"),
+            Self::OutOfMemory => f.write_str("OOM"),
+        }
+    }
+}
+
+impl FromStr for Abort {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "TIMEOUT" => Ok(Self::Timeout),
+            "OOM" => Ok(Self::OutOfMemory),
+            _ => Err(format!("unknown abort reason `{s}`.")),
+        }
+    }
+}
+
+impl From<Abort> for PartStatus {
+    fn from(value: Abort) -> Self {
+        match value {
+            Abort::Timeout => Self::Timeout,
+            Abort::OutOfMemory => Self::OutOfMemory,
+        }
+    }
+}
+
+/// The `part` number used for records of the parse phase of a solution.
+pub const PARSE_PART: u8 = 0;
+
+/// Label of a part in output, e.g. `Part 1` or `Parse` for the [`PARSE_PART`].
+#[must_use]
+pub fn part_label(part: u8) -> String {
+    if part == PARSE_PART {
+        "Parse".into()
+    } else {
+        format!("Part {part}")
+    }
+}
+
+/// Represents the result of running a single part of a solution.
+/// Can be serialized from / to a single-line JSON object.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PartRecord {
+    pub day: Day,
+    /// The part number, or [`PARSE_PART`] for the parse phase.
+    pub part: u8,
+    pub answer: Option<String>,
+    pub duration: Duration,
+    pub samples: u128,
+    pub status: PartStatus,
+    /// Message of the error returned by a failed part.
+    pub error: Option<String>,
+    /// Distribution of benchmark samples, only present for timed runs.
+    pub stats: Option<BenchStats>,
+    /// Peak resident memory of the day in bytes up to the end of this part, only present for
+    /// timed runs.
+    pub peak_rss: Option<u64>,
+    /// Heap usage of the part, only present for `--heap` runs.
+    pub heap: Option<HeapStats>,
+}
+
+impl PartRecord {
+    /// Serialize the record to a single line of JSON.
+    #[must_use]
+    pub fn to_json_line(&self) -> String {
+        JsonValue::from(self)
+            .stringify()
+            .expect("part records only contain serializable values")
+    }
+}
+
+impl From<&PartRecord> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &PartRecord) -> Self {
+        let mut map: HashMap<String, Self> = HashMap::new();
+
+        map.insert("day".into(), Self::String(value.day.to_string()));
+        map.insert("part".into(), Self::Number(f64::from(value.part)));
+        map.insert(
+            "answer".into(),
+            value.answer.clone().map_or(Self::Null, Self::String),
+        );
+        map.insert(
+            "duration_nanos".into(),
+            Self::Number(value.duration.as_nanos() as f64),
+        );
+        map.insert("samples".into(), Self::Number(value.samples as f64));
+        map.insert("status".into(), Self::String(value.status.to_string()));
+        map.insert(
+            "error".into(),
+            value.error.clone().map_or(Self::Null, Self::String),
+        );
+        map.insert(
+            "stats".into(),
+            value.stats.as_ref().map_or(Self::Null, Self::from),
+        );
+        map.insert(
+            "peak_rss".into(),
+            value
+                .peak_rss
+                .map_or(Self::Null, |bytes| Self::Number(bytes as f64)),
+        );
+        map.insert(
+            "heap".into(),
+            value.heap.as_ref().map_or(Self::Null, Self::from),
+        );
+
+        Self::Object(map)
+    }
+}
+
+impl TryFrom<&str> for PartRecord {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(value).or(Err("not valid JSON."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected record to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected record.day to be a Day struct.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u8)
+            .ok_or("Expected record.part to be a number.")?;
+
+        let answer = json
+            .get("answer")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected record.answer to be null or string.")?;
+
+        let duration = json
+            .get("duration_nanos")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| Duration::from_nanos(*v as u64))
+            .ok_or("Expected record.duration_nanos to be a number.")?;
+
+        let samples = json
+            .get("samples")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u128)
+            .ok_or("Expected record.samples to be a number.")?;
+
+        let status = json
+            .get("status")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected record.status to be a string.")?
+            .parse()?;
+
+        // NOTE: the error and memory usage are optional to stay compatible with records of older
+        // solution bins.
+        let error = json
+            .get("error")
+            .and_then(|v| if v.is_null() { None } else { v.get::<String>() });
+
+        let peak_rss = json
+            .get("peak_rss")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u64);
+
+        let bench_stats = match json.get("stats") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(BenchStats::try_from(v)?),
+        };
+
+        let heap = match json.get("heap") {
+            Some(v) if !v.is_null() => Some(HeapStats::try_from(v)?),
+            _ => None,
+        };
+
+        Ok(Self {
+            day,
+            part,
+            answer: answer.cloned(),
+            duration,
+            samples,
+            status,
+            error: error.cloned(),
+            stats: bench_stats,
+            peak_rss,
+            heap,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{Abort, OutputFormat, PartRecord, PartStatus};
+    use crate::{
+        day,
+        template::{memory::HeapStats, stats::BenchStats},
+    };
+
+    #[test]
+    fn parses_output_formats() {
+        assert_eq!("text".parse::<OutputFormat>().unwrap(), OutputFormat::Text);
+        assert_eq!("json".parse::<OutputFormat>().unwrap(), OutputFormat::Json);
+        assert!("yaml".parse::<OutputFormat>().is_err());
+    }
+
+    #[test]
+    fn roundtrips_abort_reasons() {
+        for abort in [Abort::Timeout, Abort::OutOfMemory] {
+            assert_eq!(abort.to_string().parse::<Abort>().unwrap(), abort);
+        }
+        assert!("oom".parse::<Abort>().is_err());
+    }
+
+    #[test]
+    fn roundtrips_records() {
+        let record = PartRecord {
+            day: day!(12),
+            part: 2,
+            answer: Some("line 1\nline 2".into()),
+            duration: Duration::from_nanos(74_130),
+            samples: 10_000,
+            status: PartStatus::Solved,
+            error: None,
+            stats: BenchStats::from_samples(&[
+                Duration::from_nanos(74_000),
+                Duration::from_nanos(74_130),
+                Duration::from_nanos(90_000),
+            ]),
+            peak_rss: Some(12 * 1024 * 1024),
+            heap: Some(HeapStats {
+                allocations: 3,
+                bytes: 2048,
+                peak_bytes: 1024,
+            }),
+        };
+        let line = record.to_json_line();
+        assert!(!line.contains('\n'));
+        assert_eq!(PartRecord::try_from(line.as_str()).unwrap(), record);
+    }
+
+    #[test]
+    fn handles_unsolved_records() {
+        let json = r#"{ "day": "01", "part": 1, "answer": null, "duration_nanos": 0, "samples": 1, "status": "unsolved" }"#;
+        let record = PartRecord::try_from(json).unwrap();
+        assert_eq!(record.answer, None);
+        assert_eq!(record.status, PartStatus::Unsolved);
+        assert_eq!(record.stats, None);
+    }
+
+    #[test]
+    fn roundtrips_failed_records() {
+        let record = PartRecord {
+            day: day!(3),
+            part: 1,
+            answer: None,
+            duration: Duration::from_nanos(120),
+            samples: 1,
+            status: PartStatus::Failed,
+            error: Some("line 4, column 2: expected a digit".into()),
+            stats: None,
+            peak_rss: None,
+            heap: None,
+        };
+        let line = record.to_json_line();
+        assert_eq!(PartRecord::try_from(line.as_str()).unwrap(), record);
+    }
+
+    #[test]
+    fn rejects_non_record_lines() {
+        assert!(PartRecord::try_from("Part 1: 0 (74.13ns @ 100000 samples)").is_err());
+        assert!(PartRecord::try_from(r#"{ "day": "01" }"#).is_err());
+    }
+}
@@ -0,0 +1,31 @@
+/// Registry of solutions that can be executed in-process.
+///
+/// Every `solution!` invocation exposes a [`Solution`] constant. With the `in-process` feature,
+/// the main binary collects these (see `build.rs`) so that multi-day commands can call each day
+/// directly instead of spawning a cargo process per day. Days run as separate binaries otherwise.
+use crate::template::record::PartRecord;
+use crate::template::{Day, Year};
+
+/// Runs one part of a solution on the given input, benching it if the flag is set.
+pub type PartFn = fn(input: &str, is_timed: bool) -> PartRecord;
+
+/// A single registered part of a solution.
+#[derive(Clone, Copy)]
+pub struct SolutionPart {
+    pub part: u8,
+    pub run: PartFn,
+}
+
+/// The registered parts for a single day.
+#[derive(Clone, Copy)]
+pub struct Solution {
+    pub year: Year,
+    pub day: Day,
+    pub parts: &'static [SolutionPart],
+}
+
+/// Look up the registered solution for a day of a year.
+#[must_use]
+pub fn find(solutions: &[Solution], year: Year, day: Day) -> Option<&Solution> {
+    solutions.iter().find(|s| s.year == year && s.day == day)
+}
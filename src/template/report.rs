@@ -0,0 +1,367 @@
+/// Export of stored timings as CSV and as a self-contained HTML page with an SVG chart.
+use std::{
+    fmt::Write,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::template::{
+    timings::{Timing, Timings},
+    Year, PARSE_PART,
+};
+
+/// Parts shown in the report, with their label and bar color.
+const SERIES: [(u8, &str, &str); 3] = [
+    (PARSE_PART, "Parse", "#9c9c9c"),
+    (1, "Part 1", "#4e79a7"),
+    (2, "Part 2", "#f28e2b"),
+];
+
+const CHART_WIDTH: f64 = 760.0;
+const LABEL_WIDTH: f64 = 70.0;
+const AXIS_HEIGHT: f64 = 30.0;
+const BAR_HEIGHT: f64 = 12.0;
+const GROUP_GAP: f64 = 10.0;
+
+/// Write `timings` to `{path}.csv` and `{path}.html`, returning the paths written.
+pub fn write(year: Year, timings: &Timings, path: &Path) -> io::Result<(PathBuf, PathBuf)> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)?;
+    }
+
+    let csv_path = path.with_extension("csv");
+    let html_path = path.with_extension("html");
+
+    fs::write(&csv_path, to_csv(timings))?;
+    fs::write(&html_path, to_html(year, timings))?;
+
+    Ok((csv_path, html_path))
+}
+
+/// One row per day, with times in nanoseconds and memory in bytes. Unknown values are empty.
+pub fn to_csv(timings: &Timings) -> String {
+    let mut csv = String::from(
+        "day,parse_nanos,part_1_nanos,part_2_nanos,total_nanos,peak_rss_bytes,allocations,allocated_bytes,peak_heap_bytes,failed,aborted\n",
+    );
+
+    for timing in &timings.data {
+        let heap = timing.heap();
+        let failed: Vec<String> = timing.failed.iter().map(ToString::to_string).collect();
+
+        let cells = [
+            timing.day.to_string(),
+            optional(part_nanos(timing, PARSE_PART).map(f64::round)),
+            optional(part_nanos(timing, 1).map(f64::round)),
+            optional(part_nanos(timing, 2).map(f64::round)),
+            timing.total_nanos.round().to_string(),
+            optional(timing.peak_rss),
+            optional(heap.map(|heap| heap.allocations)),
+            optional(heap.map(|heap| heap.bytes)),
+            optional(heap.map(|heap| heap.peak_bytes)),
+            failed.join(";"),
+            optional(timing.aborted),
+        ];
+
+        csv.push_str(&cells.join(","));
+        csv.push('\n');
+    }
+
+    csv
+}
+
+/// A standalone HTML page with a log-scale bar chart and a table of the timings.
+pub fn to_html(year: Year, timings: &Timings) -> String {
+    let mut html = String::new();
+
+    let _ = write!(
+        html,
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>Advent of Code {year} Benchmarks</title>
+<style>
+body {{ font-family: -apple-system, "Segoe UI", Helvetica, Arial, sans-serif; margin: 2rem; color: #222; }}
+table {{ border-collapse: collapse; margin-top: 1.5rem; }}
+th, td {{ padding: 0.3rem 0.8rem; border-bottom: 1px solid #ddd; text-align: right; }}
+th:first-child, td:first-child {{ text-align: left; }}
+svg text {{ font-size: 11px; fill: #444; }}
+</style>
+</head>
+<body>
+<h1>Advent of Code {year} Benchmarks</h1>
+<p>Total: {:.2}ms</p>
+"#,
+        timings.total_millis()
+    );
+
+    html.push_str(&chart(timings));
+    html.push_str(&table(timings));
+    html.push_str("</body>\n</html>\n");
+
+    html
+}
+
+/// Horizontal bars per day and part, on a logarithmic time axis.
+#[allow(clippy::cast_precision_loss)]
+fn chart(timings: &Timings) -> String {
+    let values: Vec<f64> = timings
+        .data
+        .iter()
+        .flat_map(|timing| {
+            SERIES
+                .iter()
+                .filter_map(|(part, ..)| part_nanos(timing, *part))
+        })
+        .filter(|nanos| *nanos > 0.0)
+        .collect();
+
+    if values.is_empty() {
+        return "<p>No timings to chart.</p>\n".into();
+    }
+
+    // snap the axis to whole decades around the measured values.
+    let min_exp = values
+        .iter()
+        .copied()
+        .fold(f64::INFINITY, f64::min)
+        .log10()
+        .floor();
+    let max_exp = values
+        .iter()
+        .copied()
+        .fold(0.0, f64::max)
+        .log10()
+        .ceil()
+        .max(min_exp + 1.0);
+
+    let plot_width = CHART_WIDTH - LABEL_WIDTH;
+    let x = |nanos: f64| {
+        ((nanos.log10() - min_exp) / (max_exp - min_exp)).mul_add(plot_width, LABEL_WIDTH)
+    };
+
+    let group_height = BAR_HEIGHT.mul_add(SERIES.len() as f64, GROUP_GAP);
+    let height = group_height.mul_add(timings.data.len() as f64, AXIS_HEIGHT) + 20.0;
+
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{CHART_WIDTH}" height="{height}" viewBox="0 0 {CHART_WIDTH} {height}">"#
+    );
+
+    // decade grid lines and labels.
+    #[allow(clippy::cast_possible_truncation)]
+    for exp in min_exp as i32..=max_exp as i32 {
+        let pos = x(10_f64.powi(exp));
+        let _ = writeln!(
+            svg,
+            r##"<line x1="{pos:.1}" y1="{AXIS_HEIGHT}" x2="{pos:.1}" y2="{:.1}" stroke="#e5e5e5"/><text x="{pos:.1}" y="{:.1}" text-anchor="middle">{}</text>"##,
+            height - 20.0,
+            AXIS_HEIGHT - 8.0,
+            format_nanos(10_f64.powi(exp))
+        );
+    }
+
+    for (index, timing) in timings.data.iter().enumerate() {
+        let top = group_height.mul_add(index as f64, AXIS_HEIGHT);
+
+        let _ = writeln!(
+            svg,
+            r#"<text x="0" y="{:.1}">Day {}</text>"#,
+            BAR_HEIGHT.mul_add(2.0, top),
+            timing.day.into_inner()
+        );
+
+        for (row, (part, label, color)) in SERIES.iter().enumerate() {
+            let Some(nanos) = part_nanos(timing, *part).filter(|nanos| *nanos > 0.0) else {
+                continue;
+            };
+
+            let _ = writeln!(
+                svg,
+                r#"<rect x="{LABEL_WIDTH}" y="{:.1}" width="{:.1}" height="{:.1}" fill="{color}"><title>Day {} {label}: {}</title></rect>"#,
+                BAR_HEIGHT.mul_add(row as f64, top),
+                (x(nanos) - LABEL_WIDTH).max(1.0),
+                BAR_HEIGHT - 2.0,
+                timing.day.into_inner(),
+                format_nanos(nanos)
+            );
+        }
+    }
+
+    // legend below the bars.
+    for (index, (_, label, color)) in SERIES.iter().enumerate() {
+        let left = 90.0_f64.mul_add(index as f64, LABEL_WIDTH);
+        let _ = writeln!(
+            svg,
+            r#"<rect x="{left}" y="{:.1}" width="10" height="10" fill="{color}"/><text x="{:.1}" y="{:.1}">{label}</text>"#,
+            height - 14.0,
+            left + 14.0,
+            height - 5.0
+        );
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn table(timings: &Timings) -> String {
+    let mut table = String::from(
+        "<table>\n<tr><th>Day</th><th>Parse</th><th>Part 1</th><th>Part 2</th><th>Memory</th></tr>\n",
+    );
+
+    for timing in &timings.data {
+        let cell = |time: Option<&String>| {
+            time.map_or_else(
+                || timing.aborted.map_or_else(|| "-".into(), |a| a.to_string()),
+                |time| escape(time),
+            )
+        };
+
+        let _ = writeln!(
+            table,
+            "<tr><td>Day {}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+            timing.day.into_inner(),
+            cell(timing.parse.as_ref()),
+            cell(timing.part_1.as_ref()),
+            cell(timing.part_2.as_ref()),
+            timing
+                .peak_rss
+                .map_or_else(|| "-".into(), crate::template::memory::format_bytes)
+        );
+    }
+
+    table.push_str("</table>\n");
+    table
+}
+
+/// Time of a part in nanoseconds, preferring the median of its stats over the stored string.
+fn part_nanos(timing: &Timing, part: u8) -> Option<f64> {
+    let (time, stats) = match part {
+        PARSE_PART => (&timing.parse, &timing.parse_stats),
+        1 => (&timing.part_1, &timing.part_1_stats),
+        2 => (&timing.part_2, &timing.part_2_stats),
+        _ => return None,
+    };
+
+    #[allow(clippy::cast_precision_loss)]
+    stats
+        .map(|stats| stats.median.as_nanos() as f64)
+        .or_else(|| time.as_deref().and_then(parse_duration))
+}
+
+/// Parse a duration formatted with `{:.1?}`, e.g. `74.1ms`, into nanoseconds.
+fn parse_duration(s: &str) -> Option<f64> {
+    let split = s.find(|c: char| !c.is_ascii_digit() && c != '.')?;
+    let (value, unit) = s.split_at(split);
+
+    let factor = match unit {
+        "ns" => 1.0,
+        "µs" => 1e3,
+        "ms" => 1e6,
+        "s" => 1e9,
+        _ => return None,
+    };
+
+    value.parse::<f64>().ok().map(|value| value * factor)
+}
+
+fn format_nanos(nanos: f64) -> String {
+    match nanos {
+        n if n >= 1e9 => format!("{}s", n / 1e9),
+        n if n >= 1e6 => format!("{}ms", n / 1e6),
+        n if n >= 1e3 => format!("{}µs", n / 1e3),
+        n => format!("{n}ns"),
+    }
+}
+
+fn optional(value: Option<impl ToString>) -> String {
+    value.map(|value| value.to_string()).unwrap_or_default()
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_duration, to_csv, to_html};
+    use crate::{
+        day,
+        template::{
+            record::Abort,
+            timings::{Timing, Timings},
+        },
+        year,
+    };
+
+    fn timing(day: crate::template::Day, part_1: &str, part_2: Option<&str>) -> Timing {
+        Timing {
+            day,
+            parse: None,
+            part_1: Some(part_1.into()),
+            part_2: part_2.map(Into::into),
+            parse_stats: None,
+            part_1_stats: None,
+            part_2_stats: None,
+            parse_heap: None,
+            part_1_heap: None,
+            part_2_heap: None,
+            failed: vec![],
+            aborted: None,
+            peak_rss: None,
+            total_nanos: 0.0,
+        }
+    }
+
+    fn get_mock_timings() -> Timings {
+        Timings {
+            data: vec![
+                Timing {
+                    peak_rss: Some(4096),
+                    total_nanos: 74_130_074.0,
+                    ..timing(day!(1), "74.0ns", Some("74.1ms"))
+                },
+                Timing {
+                    aborted: Some(Abort::Timeout),
+                    ..timing(day!(2), "1.5µs", None)
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn parses_formatted_durations() {
+        assert_eq!(parse_duration("74.0ns"), Some(74.0));
+        assert_eq!(parse_duration("1.5µs"), Some(1500.0));
+        assert_eq!(parse_duration("74.1ms"), Some(74_100_000.0));
+        assert_eq!(parse_duration("2.0s"), Some(2e9));
+        assert_eq!(parse_duration("fast"), None);
+    }
+
+    #[test]
+    fn exports_csv() {
+        let csv = to_csv(&get_mock_timings());
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("day,parse_nanos,part_1_nanos,part_2_nanos"));
+        assert_eq!(lines[1], "01,,74,74100000,74130074,4096,,,,,");
+        assert_eq!(lines[2], "02,,1500,,0,,,,,,TIMEOUT");
+    }
+
+    #[test]
+    fn exports_html_with_chart() {
+        let html = to_html(year!(2021), &get_mock_timings());
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<svg"));
+        // one bar per measured part.
+        assert_eq!(html.matches("</title></rect>").count(), 3);
+        assert!(html.contains("<title>Day 1 Part 2: 74.1ms</title>"));
+        assert!(html.contains("<td>Day 2</td><td>TIMEOUT</td><td>1.5µs</td><td>TIMEOUT</td>"));
+        assert!(!html.contains("http-equiv") && !html.contains("<script"));
+    }
+}
@@ -1,52 +1,362 @@
-use std::{collections::HashSet, io};
+use std::{
+    collections::{BTreeMap, HashSet},
+    io,
+    path::Path,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc, Mutex,
+    },
+    thread,
+    time::Duration,
+};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{
+    runner::print_record, Day, OutputFormat, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
 
 use super::{
-    all_days,
+    all_days, memory,
+    record::{Abort, PartRecord},
+    registry::{self, Solution},
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+/// Run a set of days, preferring registered in-process solutions and falling back to
+/// spawning the solution binary for days that are not part of `solutions`.
+///
+/// With `jobs > 1`, untimed days run concurrently and their output is buffered and printed
+/// in day order. Timed runs are always sequential to keep benchmarks free of contention.
+///
+/// If any of `limits` is set, every day runs as a child process and days that exceed a limit
+/// are stopped and reported as aborted instead of blocking the run.
+///
+/// With `is_heap`, every day runs as a child process built with the `dhat-heap` feature and
+/// reports the heap usage of each part.
+///
+/// Returns the timings of every day that ran. Their total is only printed for timed runs.
+///
+/// # Errors
+///
+/// Will return an error if the process of a day could not be run. Days that run before are
+/// still printed.
+#[allow(clippy::too_many_arguments)]
+pub fn run_multi(
+    year: Year,
+    days_to_run: &HashSet<Day>,
+    solutions: &[Solution],
+    is_release: bool,
+    is_timed: bool,
+    is_heap: bool,
+    jobs: usize,
+    limits: Limits,
+    format: OutputFormat,
+) -> Result<Timings, Error> {
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+    let mut aborted_days: Vec<(Day, Abort)> = vec![];
 
     let mut need_space = false;
 
     // NOTE: use non-duplicate, sorted day values.
-    all_days()
-        .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
-            }
-            need_space = true;
+    let days: Vec<Day> = all_days().filter(|day| days_to_run.contains(day)).collect();
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+    // limits are enforced on processes and the heap profiler is a compile-time feature, so
+    // registered days run as children as well.
+    let run_children = limits.is_set() || is_heap;
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+    if run_children {
+        // build up front, so that compile times do not count towards the timeout.
+        child_commands::build(year, &days, is_release, is_heap);
+    }
 
-            if output.is_empty() {
+    let run_day = |day: Day, emit: &Emit| {
+        if run_children {
+            child_commands::run_solution(
+                year, day, None, is_timed, is_release, is_heap, limits, emit,
+            )
+        } else {
+            Ok(DayRun {
+                records: run_day(year, day, solutions, is_release, is_timed, emit)?,
+                aborted: None,
+            })
+        }
+    };
+
+    let mut finish_day = |day: Day, run: DayRun| {
+        if let Some(abort) = run.aborted {
+            print_abort(day, abort, &run.records, limits, format);
+            aborted_days.push((day, abort));
+        }
+
+        if run.records.is_empty() && run.aborted.is_none() {
+            if !format.is_json() {
                 println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
+            }
+        } else {
+            let mut timing = child_commands::collect_timing(&run.records, day);
+            timing.aborted = run.aborted;
+
+            if let Some(bytes) = timing.peak_rss.filter(|_| !format.is_json()) {
+                println!(
+                    "{ANSI_ITALIC}Peak memory: {}{ANSI_RESET}",
+                    memory::format_bytes(bytes)
+                );
+            }
+
+            timings.push(timing);
+        }
+    };
+
+    if is_timed || jobs <= 1 {
+        for day in days {
+            print_header(day, format, &mut need_space);
+            let run = run_day(day, &|output| output.print(format))?;
+            finish_day(day, run);
+        }
+    } else {
+        let mut error = None;
+
+        run_parallel(&days, jobs, run_day, |day, outputs, run| {
+            print_header(day, format, &mut need_space);
+            for output in &outputs {
+                output.print(format);
+            }
+            match run {
+                Ok(run) => finish_day(day, run),
+                Err(e) => {
+                    error.get_or_insert(e);
+                }
             }
         });
 
-    if is_timed {
-        let timings = Timings { data: timings };
+        if let Some(e) = error {
+            return Err(e);
+        }
+    }
+
+    if !aborted_days.is_empty() && !format.is_json() {
+        let summary: Vec<String> = aborted_days
+            .iter()
+            .map(|(day, abort)| format!("Day {day} ({abort})"))
+            .collect();
+        println!("\n{ANSI_BOLD}Aborted:{ANSI_RESET} {}", summary.join(", "));
+    }
+
+    let timings = Timings { data: timings };
+
+    if is_timed && !format.is_json() {
         let total_millis = timings.total_millis();
         println!(
             "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
         );
-        Some(timings)
+    }
+
+    Ok(timings)
+}
+
+/// Run a single day, in-process if it is registered in `solutions`, emitting its output.
+///
+/// # Errors
+///
+/// Will return an error if the solution binary of an unregistered day could not be run.
+pub fn run_day(
+    year: Year,
+    day: Day,
+    solutions: &[Solution],
+    is_release: bool,
+    is_timed: bool,
+    emit: &Emit,
+) -> Result<Vec<PartRecord>, Error> {
+    run_day_on(year, day, None, solutions, is_release, is_timed, emit)
+}
+
+/// Run a single day like [`run_day`], reading the input file at `input` if given.
+///
+/// # Errors
+///
+/// Will return an error if the solution binary of an unregistered day could not be run.
+pub fn run_day_on(
+    year: Year,
+    day: Day,
+    input: Option<&Path>,
+    solutions: &[Solution],
+    is_release: bool,
+    is_timed: bool,
+    emit: &Emit,
+) -> Result<Vec<PartRecord>, Error> {
+    registry::find(solutions, year, day).map_or_else(
+        || {
+            child_commands::run_solution(
+                year,
+                day,
+                input,
+                is_timed,
+                is_release,
+                false,
+                Limits::default(),
+                emit,
+            )
+            .map(|run| run.records)
+        },
+        |solution| Ok(in_process::run_solution(solution, input, is_timed, emit)),
+    )
+}
+
+/// Resource limits of each day in a run.
+///
+/// Limits can only be enforced on separate processes, so days run as child processes when
+/// any limit is set, even if they are registered in-process.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Limits {
+    /// Wall-clock time after which a day is killed.
+    pub timeout: Option<Duration>,
+    /// Maximum address space of a day, in bytes.
+    pub memory: Option<u64>,
+}
+
+impl Limits {
+    #[must_use]
+    pub const fn is_set(&self) -> bool {
+        self.timeout.is_some() || self.memory.is_some()
+    }
+}
+
+/// The records of a day, and why it was stopped if it did not finish.
+pub struct DayRun {
+    pub records: Vec<PartRecord>,
+    pub aborted: Option<Abort>,
+}
+
+fn print_abort(
+    day: Day,
+    abort: Abort,
+    records: &[PartRecord],
+    limits: Limits,
+    format: OutputFormat,
+) {
+    let reason = match abort {
+        Abort::Timeout => format!("stopped after {:.1?}", limits.timeout.unwrap_or_default()),
+        Abort::OutOfMemory => format!(
+            "exceeded the memory limit of {} MiB",
+            limits.memory.unwrap_or_default() / (1024 * 1024)
+        ),
+    };
+
+    // keep stdout parseable in JSON mode, the aborted parts get a record instead.
+    if format.is_json() {
+        eprintln!("{abort}: {reason}");
+        for record in aborted_records(day, abort, records, &reason) {
+            println!("{}", record.to_json_line());
+        }
     } else {
-        None
+        println!("{ANSI_BOLD}{abort}{ANSI_RESET}: {reason}");
+    }
+}
+
+/// Records of the parts of a day that did not finish before it was aborted.
+fn aborted_records(
+    day: Day,
+    abort: Abort,
+    records: &[PartRecord],
+    reason: &str,
+) -> Vec<PartRecord> {
+    [1, 2]
+        .into_iter()
+        .filter(|part| !records.iter().any(|record| record.part == *part))
+        .map(|part| PartRecord {
+            day,
+            part,
+            answer: None,
+            duration: Duration::ZERO,
+            samples: 0,
+            status: abort.into(),
+            error: Some(reason.to_string()),
+            stats: None,
+            peak_rss: None,
+            heap: None,
+        })
+        .collect()
+}
+
+fn print_header(day: Day, format: OutputFormat, need_space: &mut bool) {
+    if format.is_json() {
+        return;
+    }
+
+    if *need_space {
+        println!();
+    }
+    *need_space = true;
+
+    println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+    println!("------");
+}
+
+/// Run days on a pool of `jobs` worker threads, handing each day's buffered output to
+/// `on_finished` in day order as soon as it and all days before it have completed.
+fn run_parallel<R: Send>(
+    days: &[Day],
+    jobs: usize,
+    run_day: impl Fn(Day, &Emit) -> R + Sync,
+    mut on_finished: impl FnMut(Day, Vec<DayOutput>, R),
+) {
+    let next_day = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(days.len()) {
+            let tx = tx.clone();
+            let (next_day, run_day) = (&next_day, &run_day);
+
+            scope.spawn(move || loop {
+                let index = next_day.fetch_add(1, Ordering::Relaxed);
+                let Some(&day) = days.get(index) else {
+                    break;
+                };
+
+                let buffer = Mutex::new(vec![]);
+                let result = run_day(day, &|output| buffer.lock().unwrap().push(output));
+                tx.send((index, buffer.into_inner().unwrap(), result))
+                    .unwrap();
+            });
+        }
+
+        drop(tx);
+
+        let mut pending = BTreeMap::new();
+        let mut next_to_print = 0;
+
+        for (index, outputs, result) in rx {
+            pending.insert(index, (outputs, result));
+            while let Some((outputs, result)) = pending.remove(&next_to_print) {
+                on_finished(days[next_to_print], outputs, result);
+                next_to_print += 1;
+            }
+        }
+    });
+}
+
+/// A piece of output produced while running a day.
+pub enum DayOutput {
+    Record(Box<PartRecord>),
+    Stdout(String),
+    Stderr(String),
+}
+
+impl DayOutput {
+    fn print(&self, format: OutputFormat) {
+        match self {
+            Self::Record(record) => print_record(record, format),
+            // keep stdout parseable by moving stray solution output to stderr.
+            Self::Stdout(line) if format.is_json() => eprintln!("{line}"),
+            Self::Stdout(line) => println!("{line}"),
+            Self::Stderr(line) => eprintln!("{line}"),
+        }
     }
 }
 
-#[allow(dead_code)]
+/// Receives output as a day runs, either printing it right away or buffering it.
+pub type Emit<'a> = dyn Fn(DayOutput) + Sync + 'a;
+
 #[derive(Debug)]
 pub enum Error {
     BrokenPipe,
@@ -59,136 +369,305 @@ impl From<std::io::Error> for Error {
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+/// Registered solutions are linked into the main binary and can be called directly,
+/// which avoids a cargo invocation per day.
+pub mod in_process {
+    use std::{
+        fs,
+        panic::{self, AssertUnwindSafe},
+        path::Path,
+    };
+
+    use super::{DayOutput, Emit, PartRecord, Solution};
+    use crate::template::{runner::panicked_record, try_read_file};
+
+    /// Run every registered part of a solution, emitting its results.
+    pub fn run_solution(
+        solution: &Solution,
+        input: Option<&Path>,
+        is_timed: bool,
+        emit: &Emit,
+    ) -> Vec<PartRecord> {
+        let input = match input.map_or_else(
+            || try_read_file("inputs", solution.year, solution.day),
+            fs::read_to_string,
+        ) {
+            Ok(input) => input,
+            Err(e) => {
+                emit(DayOutput::Stderr(format!("Could not open input file: {e}")));
+                return vec![];
+            }
+        };
+
+        // NOTE: days share the process and may run in parallel, so the peak memory of the
+        // process is not attributable to a single day and is left out of the records.
+        solution
+            .parts
+            .iter()
+            .map(|part| {
+                // a panicking part should not take the remaining days down with it.
+                panic::catch_unwind(AssertUnwindSafe(|| (part.run)(&input, is_timed)))
+                    .unwrap_or_else(|_| panicked_record(solution.day, part.part))
+            })
+            .inspect(|record| emit(DayOutput::Record(Box::new(record.clone()))))
+            .collect()
+    }
 }
 
-/// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// Solutions that are not registered live in isolated binaries.
+/// This module encapsulates interaction with these binaries, both invoking them as well as collecting the records they emit.
 pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use super::{DayOutput, DayRun, Emit, Error, Limits, PartRecord};
+    use crate::template::{
+        paths,
+        record::{Abort, PartStatus},
+        runner::MEMORY_LIMIT_ENV,
+        Day, Year, PARSE_PART,
+    };
     use std::{
         io::{BufRead, BufReader},
         path::Path,
         process::{Command, Stdio},
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            mpsc::{self, RecvTimeoutError},
+            Mutex,
+        },
         thread,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+    /// Start of the message printed by the standard library when an allocation fails.
+    const ALLOC_ERROR_PREFIX: &str = "memory allocation of";
+
+    /// Build the solution bins of `days` that have been scaffolded.
+    /// Build errors are not reported here, as they surface again when the day runs.
+    pub fn build(year: Year, days: &[Day], is_release: bool, is_heap: bool) {
+        let bin_names: Vec<String> = days
+            .iter()
+            .filter(|&&day| Path::new(&paths::bin_file(year, day)).exists())
+            .map(|&day| paths::bin_name(year, day))
+            .collect();
+
+        if bin_names.is_empty() {
+            return;
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        let mut args = vec!["build", "--quiet"];
+        args.extend(profile_args(is_release, is_heap));
 
-        if is_release {
-            args.push("--release");
+        for bin_name in &bin_names {
+            args.extend(["--bin", bin_name]);
         }
 
+        let _ = Command::new("cargo")
+            .current_dir(paths::root())
+            .args(&args)
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status();
+    }
+
+    /// Run the solution bin for a given day, emitting its results and any other output.
+    ///
+    /// The child is killed once it exceeds the timeout of `limits`, and its address space is
+    /// limited by the solution binary itself so that the limit does not apply to cargo.
+    #[allow(clippy::too_many_arguments)]
+    pub fn run_solution(
+        year: Year,
+        day: Day,
+        input: Option<&Path>,
+        is_timed: bool,
+        is_release: bool,
+        is_heap: bool,
+        limits: Limits,
+        emit: &Emit,
+    ) -> Result<DayRun, Error> {
+        // skip command invocation for days that have not been scaffolded yet.
+        if !Path::new(&paths::bin_file(year, day)).exists() {
+            return Ok(DayRun {
+                records: vec![],
+                aborted: None,
+            });
+        }
+
+        let bin_name = paths::bin_name(year, day);
+        let mut args = vec!["run", "--quiet", "--bin", &bin_name];
+        args.extend(profile_args(is_release, is_heap));
+
+        // children always report structured records, which are rendered by the caller.
+        args.extend(["--", "--format", "json"]);
+
         if is_timed {
             // mirror `--time` flag to child invocations.
-            args.push("--");
             args.push("--time");
         }
 
+        if is_heap {
+            args.push("--heap");
+        }
+
+        // cargo runs in the project root, so relative inputs are resolved before.
+        let input = input.map(std::path::absolute).transpose()?;
+        let input = input.as_ref().map(|input| input.to_string_lossy());
+        if let Some(input) = &input {
+            args.extend(["--input", input]);
+        }
+
         // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        // forward output to the caller while grabbing part records.
 
-        let mut cmd = Command::new("cargo")
+        let mut command = Command::new("cargo");
+        command
+            .current_dir(paths::root())
             .args(&args)
             .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
+            .stderr(Stdio::piped());
+
+        if let Some(memory) = limits.memory {
+            command.env(MEMORY_LIMIT_ENV, memory.to_string());
+        }
+
+        let mut cmd = command.spawn()?;
 
         let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
         let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
 
-        let mut output = vec![];
+        let mut records = vec![];
+
+        let child = Mutex::new(cmd);
+        let timed_out = AtomicBool::new(false);
+        let out_of_memory = AtomicBool::new(false);
+        let (done, finished) = mpsc::channel::<()>();
+
+        thread::scope(|scope| {
+            if let Some(timeout) = limits.timeout {
+                let (child, timed_out) = (&child, &timed_out);
+                scope.spawn(move || {
+                    // `done` is dropped once stdout closes, i.e. when the child has exited.
+                    if finished.recv_timeout(timeout) == Err(RecvTimeoutError::Timeout) {
+                        timed_out.store(true, Ordering::Relaxed);
+                        let _ = child.lock().unwrap().kill();
+                    }
+                });
+            }
 
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
+            scope.spawn(|| {
+                lossy_lines(stderr).for_each(|line| {
+                    if line.starts_with(ALLOC_ERROR_PREFIX) {
+                        out_of_memory.store(true, Ordering::Relaxed);
+                    }
+                    emit(DayOutput::Stderr(line));
+                });
             });
+
+            for line in lossy_lines(stdout) {
+                match PartRecord::try_from(line.as_str()) {
+                    Ok(record) => {
+                        emit(DayOutput::Record(Box::new(record.clone())));
+                        records.push(record);
+                    }
+                    Err(_) => emit(DayOutput::Stdout(line)),
+                }
+            }
+
+            drop(done);
         });
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
-        }
+        let status = child.into_inner().unwrap().wait()?;
+
+        let aborted = if timed_out.into_inner() {
+            Some(Abort::Timeout)
+        } else if out_of_memory.into_inner() && !status.success() {
+            Some(Abort::OutOfMemory)
+        } else {
+            None
+        };
 
-        thread.join().unwrap();
-        cmd.wait()?;
+        Ok(DayRun { records, aborted })
+    }
 
-        Ok(output)
+    /// Lines of the output of a child. Output that is not valid UTF-8 is decoded lossily, and
+    /// reading stops at the first error instead of panicking.
+    fn lossy_lines(reader: impl BufRead) -> impl Iterator<Item = String> {
+        reader.split(b'\n').map_while(Result::ok).map(|mut line| {
+            if line.last() == Some(&b'\r') {
+                line.pop();
+            }
+            String::from_utf8_lossy(&line).into_owned()
+        })
+    }
+
+    /// Cargo flags selecting the profile and features of solution bins.
+    const fn profile_args(is_release: bool, is_heap: bool) -> &'static [&'static str] {
+        if is_heap {
+            &["--profile", "dhat", "--features", "dhat-heap"]
+        } else if is_release {
+            &["--release"]
+        } else {
+            &[]
+        }
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
+    pub fn collect_timing(records: &[PartRecord], day: Day) -> super::Timing {
         let mut timings = super::Timing {
             day,
+            parse: None,
             part_1: None,
             part_2: None,
+            parse_stats: None,
+            part_1_stats: None,
+            part_2_stats: None,
+            parse_heap: None,
+            part_1_heap: None,
+            part_2_heap: None,
+            failed: vec![],
+            aborted: None,
+            peak_rss: None,
             total_nanos: 0_f64,
         };
 
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
+        timings.peak_rss = records.iter().filter_map(|record| record.peak_rss).max();
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
+        timings.failed = records
+            .iter()
+            .filter(|record| record.status == PartStatus::Failed)
+            .map(|record| record.part)
+            .collect();
 
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
+        records
+            .iter()
+            .filter(|record| record.status == PartStatus::Solved)
+            .for_each(|record| {
+                let timing_str = format!("{:.1?}", record.duration);
+
+                match record.part {
+                    PARSE_PART => {
+                        timings.parse = Some(timing_str);
+                        timings.parse_stats = record.stats;
+                        timings.parse_heap = record.heap;
+                    }
+                    1 => {
+                        timings.part_1 = Some(timing_str);
+                        timings.part_1_stats = record.stats;
+                        timings.part_1_heap = record.heap;
+                    }
+                    2 => {
+                        timings.part_2 = Some(timing_str);
+                        timings.part_2_stats = record.stats;
+                        timings.part_2_heap = record.heap;
+                    }
+                    _ => {}
                 }
 
-                timings.total_nanos += nanos;
+                #[allow(clippy::cast_precision_loss)]
+                {
+                    timings.total_nanos += record.duration.as_nanos() as f64;
+                }
             });
 
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
-
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .next_back()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
-    }
-
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
     #[cfg(feature = "test_lib")]
     macro_rules! assert_approx_eq {
@@ -205,53 +684,182 @@ pub mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use std::time::Duration;
+
+        use super::{collect_timing, lossy_lines};
+
+        use crate::{
+            day,
+            template::record::{PartRecord, PartStatus},
+        };
 
-        use crate::day;
+        fn record(part: u8, answer: Option<&str>, nanos: u64, samples: u128) -> PartRecord {
+            PartRecord {
+                day: day!(1),
+                part,
+                answer: answer.map(Into::into),
+                duration: Duration::from_nanos(nanos),
+                samples,
+                status: if answer.is_some() {
+                    PartStatus::Solved
+                } else {
+                    PartStatus::Unsolved
+                },
+                error: None,
+                stats: None,
+                peak_rss: None,
+                heap: None,
+            }
+        }
 
         #[test]
-        fn parses_execution_times() {
-            let res = parse_exec_time(
+        fn collects_execution_times() {
+            let res = collect_timing(
                 &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
+                    record(1, Some("0"), 74, 100_000),
+                    record(2, Some("10"), 74_130_000, 99_999),
                 ],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_approx_eq!(res.total_nanos, 74130074_f64);
+            assert_eq!(res.part_1.unwrap(), "74.0ns");
+            assert_eq!(res.part_2.unwrap(), "74.1ms");
         }
 
         #[test]
-        fn parses_with_patterns_in_input() {
-            let res = parse_exec_time(
+        fn collects_multiline_answers() {
+            let res = collect_timing(
                 &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
+                    record(1, Some("@ @ @ ( ) ms"), 2_000_000_000, 5),
+                    record(2, Some("#..#\n#..#"), 100_000_000, 1),
                 ],
                 day!(1),
             );
             assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_eq!(res.part_1.unwrap(), "2.0s");
+            assert_eq!(res.part_2.unwrap(), "100.0ms");
         }
 
         #[test]
-        fn parses_missing_parts() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
+        fn collects_missing_parts() {
+            let res = collect_timing(&[record(1, None, 10, 1), record(2, None, 10, 1)], day!(1));
             assert_approx_eq!(res.total_nanos, 0_f64);
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
+            assert!(res.failed.is_empty());
+        }
+
+        #[test]
+        fn collects_failed_parts() {
+            let failed = PartRecord {
+                status: PartStatus::Failed,
+                error: Some("bad input".into()),
+                ..record(2, None, 10, 1)
+            };
+            let res = collect_timing(&[record(1, Some("1"), 10, 1), failed], day!(1));
+            assert_eq!(res.part_2, None);
+            assert_eq!(res.failed, vec![2]);
+        }
+
+        #[test]
+        fn collects_peak_memory() {
+            let with_memory = |part, bytes| PartRecord {
+                peak_rss: Some(bytes),
+                ..record(part, Some("1"), 10, 1)
+            };
+            let res = collect_timing(&[with_memory(1, 4096), with_memory(2, 2048)], day!(1));
+            assert_eq!(res.peak_rss, Some(4096));
+
+            let res = collect_timing(&[record(1, Some("1"), 10, 1)], day!(1));
+            assert_eq!(res.peak_rss, None);
+        }
+
+        #[test]
+        fn reads_output_that_is_not_utf8() {
+            let output: &[u8] = b"Part 1: \xff\r\nPart 2: 42\n";
+            assert_eq!(
+                lossy_lines(output).collect::<Vec<_>>(),
+                vec!["Part 1: \u{fffd}".to_string(), "Part 2: 42".into()]
+            );
         }
     }
 }
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{thread, time::Duration};
+
+    use super::{aborted_records, run_parallel, DayOutput};
+    use crate::{
+        day,
+        template::{
+            record::{Abort, PartRecord, PartStatus},
+            Day,
+        },
+    };
+
+    #[test]
+    fn runs_days_in_parallel_and_reports_in_order() {
+        let days = [day!(1), day!(2), day!(3), day!(4)];
+        let mut finished: Vec<(Day, String)> = vec![];
+
+        run_parallel(
+            &days,
+            4,
+            |day, emit| {
+                // later days finish first.
+                thread::sleep(Duration::from_millis(u64::from(5 - day.into_inner()) * 10));
+                emit(DayOutput::Stdout(format!("output of {day}")));
+                day
+            },
+            |day, outputs, result| {
+                assert_eq!(result, day);
+                let DayOutput::Stdout(line) = &outputs[0] else {
+                    panic!("expected buffered stdout");
+                };
+                finished.push((day, line.clone()));
+            },
+        );
+
+        assert_eq!(
+            finished,
+            vec![
+                (day!(1), "output of 01".into()),
+                (day!(2), "output of 02".into()),
+                (day!(3), "output of 03".into()),
+                (day!(4), "output of 04".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn records_the_parts_of_aborted_days() {
+        let finished = PartRecord {
+            day: day!(3),
+            part: 1,
+            answer: Some("42".into()),
+            duration: Duration::from_millis(2),
+            samples: 1,
+            status: PartStatus::Solved,
+            error: None,
+            stats: None,
+            peak_rss: None,
+            heap: None,
+        };
+
+        let records = aborted_records(day!(3), Abort::Timeout, &[finished], "stopped after 1.0s");
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].part, 2);
+        assert_eq!(records[0].status, PartStatus::Timeout);
+        assert!(records[0].to_json_line().contains("\"status\":\"timeout\""));
+
+        let records = aborted_records(day!(3), Abort::OutOfMemory, &[], "exceeded");
+        assert_eq!(
+            records
+                .iter()
+                .map(|r| r.status.to_string())
+                .collect::<Vec<_>>(),
+            ["oom", "oom"]
+        );
+    }
+}
@@ -1,68 +1,505 @@
 /// Encapsulates code that interacts with solution functions.
 use std::fmt::Display;
 use std::hint::black_box;
-use std::io::{stdout, Write};
-use std::process::Output;
+use std::io::{self, stdin, stdout, Write};
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{cmp, env, fs, process};
 
+use crate::template::aoc_client::{self, Verdict};
+use crate::template::commands::CommandError;
+use crate::template::config;
+use crate::template::ledger::{self, Ledger, LedgerEntry};
+use crate::template::memory::HeapStats;
+use crate::template::record::{part_label, OutputFormat, PartRecord, PartStatus, PARSE_PART};
+use crate::template::registry::Solution;
+use crate::template::stats::BenchStats;
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{
+    answers, examples, memory, paths, try_read_file, Day, Year, ANSI_ITALIC, ANSI_RESET,
+};
 
-pub fn run_part<I: Copy, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
-    let part_str = format!("Part {part}");
+/// The outcome of a solution function: an answer, no answer, or an error.
+pub enum Outcome<T> {
+    Solved(T),
+    Unsolved,
+    Failed(String),
+}
+
+impl<T> Outcome<T> {
+    /// The answer, if the function produced one.
+    pub fn solved(self) -> Option<T> {
+        match self {
+            Self::Solved(value) => Some(value),
+            Self::Unsolved | Self::Failed(_) => None,
+        }
+    }
+}
+
+/// Values that solution functions may return.
+///
+/// Implemented for `Option<T>`, where `None` marks the part as unsolved, and for
+/// `Result<T, E>`, where the error is reported and marks the part as failed.
+pub trait PartOutput {
+    type Value;
 
-    let (result, duration, samples) = run_timed(func, input, |result| {
-        print_result(result.as_ref(), &part_str, "");
+    /// Inspect the outcome without consuming the value.
+    fn outcome(&self) -> Outcome<&Self::Value>;
+
+    /// Take the value, if there is one.
+    fn into_value(self) -> Option<Self::Value>;
+}
+
+impl<T> PartOutput for Option<T> {
+    type Value = T;
+
+    fn outcome(&self) -> Outcome<&T> {
+        self.as_ref().map_or(Outcome::Unsolved, Outcome::Solved)
+    }
+
+    fn into_value(self) -> Self {
+        self
+    }
+}
+
+impl<T, E: Display> PartOutput for Result<T, E> {
+    type Value = T;
+
+    fn outcome(&self) -> Outcome<&T> {
+        match self {
+            Ok(value) => Outcome::Solved(value),
+            Err(e) => Outcome::Failed(e.to_string()),
+        }
+    }
+
+    fn into_value(self) -> Option<T> {
+        self.ok()
+    }
+}
+
+pub fn run_part<I: Copy, O: PartOutput>(
+    func: impl Fn(I) -> O,
+    input: I,
+    year: Year,
+    day: Day,
+    part: u8,
+) where
+    O::Value: Display,
+{
+    let format = output_format();
+    let is_timed = env::args().any(|x| x == "--time");
+    let part_str = part_label(part);
+
+    let (result, duration, stats, heap) = run_timed(func, input, is_timed, format, |result| {
+        if !format.is_json() {
+            print_result(result.outcome().solved(), &part_str, "");
+        }
     });
 
-    print_result(
-        result.as_ref(),
-        &part_str,
-        &format_duration(&duration, samples),
+    let mut record = to_record(result.outcome(), duration, stats, day, part);
+
+    if is_timed {
+        record.peak_rss = memory::peak_rss();
+    }
+    record.heap = heap;
+
+    print_record(&record, format);
+
+    if let Some(result) = result.into_value() {
+        let answer = result.to_string();
+        match submit_result(result, year, day, part, format) {
+            Some(Ok(submission)) if submission.verdict == Verdict::Correct => {
+                match answers::record_accepted(year, day, part, &answer) {
+                    Ok(()) => print_status(
+                        &format!("Recorded answer for day {day} part {part}."),
+                        format,
+                    ),
+                    Err(e) => eprintln!("Failed to record answer: {e}"),
+                }
+            }
+            Some(Err(e @ CommandError::Parse(_))) => exit_with(&e),
+            Some(Err(e)) => eprintln!("{e}"),
+            _ => {}
+        }
+    }
+}
+
+/// Run the parse hook of a solution, timing it like a part, and return the parsed input.
+pub fn run_parse<O: PartOutput>(
+    func: impl Fn(&str) -> O,
+    input: &str,
+    day: Day,
+) -> Option<O::Value> {
+    let format = output_format();
+    let is_timed = env::args().any(|x| x == "--time");
+
+    let (parsed, duration, stats, heap) = run_timed(func, input, is_timed, format, |_| {
+        if !format.is_json() {
+            print!("{}:", part_label(PARSE_PART));
+        }
+    });
+
+    let mut record = to_parse_record(parsed.outcome(), duration, stats, day);
+
+    if is_timed {
+        record.peak_rss = memory::peak_rss();
+    }
+    record.heap = heap;
+
+    print_record(&record, format);
+    parsed.into_value()
+}
+
+/// Report a part that could not run because its input failed to parse.
+pub fn skip_part(day: Day, part: u8) {
+    print_record(&unparsed_record(day, part), output_format());
+}
+
+/// Run the parse hook of a solution without printing anything, returning its record.
+/// Used by the in-process solution registry.
+pub fn solve_parse<O: PartOutput>(
+    func: impl Fn(&str) -> O,
+    input: &str,
+    day: Day,
+    is_timed: bool,
+) -> PartRecord {
+    let (parsed, duration, stats, _) = run_timed(func, input, is_timed, OutputFormat::Json, |_| {});
+    to_parse_record(parsed.outcome(), duration, stats, day)
+}
+
+/// Record of a part that could not run because its input failed to parse.
+#[must_use]
+pub fn unparsed_record(day: Day, part: u8) -> PartRecord {
+    to_record::<String>(
+        Outcome::Failed("input could not be parsed".into()),
+        Duration::ZERO,
+        None,
+        day,
+        part,
+    )
+}
+
+/// Record of a part that panicked.
+#[must_use]
+pub fn panicked_record(day: Day, part: u8) -> PartRecord {
+    to_record::<String>(
+        Outcome::Failed("panicked".into()),
+        Duration::ZERO,
+        None,
+        day,
+        part,
+    )
+}
+
+/// Run a solution part without printing anything, returning its record.
+/// Used by the in-process solution registry.
+pub fn solve_part<I: Copy, O: PartOutput>(
+    func: impl Fn(I) -> O,
+    input: I,
+    day: Day,
+    part: u8,
+    is_timed: bool,
+) -> PartRecord
+where
+    O::Value: Display,
+{
+    let (result, duration, stats, _) = run_timed(func, input, is_timed, OutputFormat::Json, |_| {});
+    to_record(result.outcome(), duration, stats, day, part)
+}
+
+fn to_parse_record<P>(
+    outcome: Outcome<&P>,
+    duration: Duration,
+    stats: Option<BenchStats>,
+    day: Day,
+) -> PartRecord {
+    // the parsed value is not an answer, only keep whether parsing succeeded.
+    match outcome {
+        Outcome::Solved(_) => PartRecord {
+            status: PartStatus::Solved,
+            ..to_record::<String>(Outcome::Unsolved, duration, stats, day, PARSE_PART)
+        },
+        Outcome::Unsolved => {
+            to_record::<String>(Outcome::Unsolved, duration, stats, day, PARSE_PART)
+        }
+        Outcome::Failed(error) => {
+            to_record::<String>(Outcome::Failed(error), duration, stats, day, PARSE_PART)
+        }
+    }
+}
+
+fn to_record<T: Display>(
+    outcome: Outcome<&T>,
+    duration: Duration,
+    stats: Option<BenchStats>,
+    day: Day,
+    part: u8,
+) -> PartRecord {
+    let (part_status, answer, error) = match outcome {
+        Outcome::Solved(result) => (PartStatus::Solved, Some(result.to_string()), None),
+        Outcome::Unsolved => (PartStatus::Unsolved, None, None),
+        Outcome::Failed(error) => (PartStatus::Failed, None, Some(error)),
+    };
+
+    PartRecord {
+        day,
+        part,
+        answer,
+        duration,
+        samples: stats.map_or(1, |stats| stats.samples),
+        status: part_status,
+        error,
+        stats,
+        peak_rss: None,
+        heap: None,
+    }
+}
+
+/// Print the final result of a solution part in the given output format.
+pub fn print_record(record: &PartRecord, format: OutputFormat) {
+    match format {
+        OutputFormat::Json => println!("{}", record.to_json_line()),
+        OutputFormat::Text => {
+            let duration_str =
+                format_duration(&record.duration, record.samples, record.stats.as_ref());
+
+            if let Some(error) = &record.error {
+                print!("\r");
+                println!(
+                    "{}: ✖ {ANSI_BOLD}failed{ANSI_RESET}{duration_str}",
+                    part_label(record.part)
+                );
+                println!("  {error}");
+            } else if record.part == PARSE_PART {
+                // the parse phase has no answer, only report whether it succeeded.
+                let failed = if record.status == PartStatus::Solved {
+                    ""
+                } else {
+                    " ✖"
+                };
+                print!("\r");
+                println!("{}:{failed}{duration_str}", part_label(PARSE_PART));
+            } else {
+                print_result(
+                    record.answer.as_ref(),
+                    &part_label(record.part),
+                    &duration_str,
+                );
+            }
+
+            if let Some(stats) = &record.stats {
+                print_stats(stats);
+            }
+
+            if let Some(heap) = &record.heap {
+                print_heap(heap);
+            }
+        }
+    }
+}
+
+/// Run a part on an example file and assert that it produces the answer declared in the
+/// file's header. Used by the tests generated with [`example_tests!`](crate::example_tests).
+///
+/// # Panics
+///
+/// Will panic if the example cannot be read, declares no answer for the part or the part
+/// produces a different answer.
+pub fn check_example(solution: &Solution, file_name: &str, part: u8) {
+    let path = format!(
+        "{}/{file_name}",
+        paths::folder_dir("examples", solution.year)
+    );
+    let text = fs::read_to_string(&path).expect("could not open example file");
+    let (answers, input) = examples::split_header(&text);
+
+    let expected = answers
+        .into_iter()
+        .find_map(|(p, answer)| (p == part).then_some(answer))
+        .unwrap_or_else(|| panic!("{path} declares no answer for part {part} yet"));
+
+    let solution_part = solution
+        .parts
+        .iter()
+        .find(|p| p.part == part)
+        .unwrap_or_else(|| panic!("the solution has no part {part}"));
+
+    let record = (solution_part.run)(input, false);
+
+    assert_eq!(
+        record.answer.as_deref(),
+        Some(expected.as_str()),
+        "{path}, part {part}{}",
+        record
+            .error
+            .map_or_else(String::new, |error| format!(": {error}"))
     );
+}
+
+/// Env var holding the address-space limit of a solution binary in bytes, set by `cargo all`
+/// and `cargo time` when running days with `--memory`.
+pub const MEMORY_LIMIT_ENV: &str = "AOC_MEMORY_LIMIT";
+
+/// Limit the address space of the current process to [`MEMORY_LIMIT_ENV`], if set.
+/// Allocations beyond the limit fail, which aborts the process.
+pub fn apply_memory_limit() {
+    let Some(bytes) = env::var(MEMORY_LIMIT_ENV)
+        .ok()
+        .and_then(|bytes| bytes.parse::<u64>().ok())
+    else {
+        return;
+    };
+
+    #[cfg(unix)]
+    {
+        let limit = libc::rlimit {
+            rlim_cur: bytes,
+            rlim_max: bytes,
+        };
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+        // SAFETY: `setrlimit` only reads the passed struct, which outlives the call.
+        if unsafe { libc::setrlimit(libc::RLIMIT_AS, &raw const limit) } != 0 {
+            eprintln!(
+                "Failed to apply memory limit: {}",
+                io::Error::last_os_error()
+            );
+        }
     }
+
+    #[cfg(not(unix))]
+    eprintln!("Memory limits of {bytes} bytes are only supported on unix.");
+}
+
+/// Read the puzzle input of a day. Reads the file passed via `--input` instead if present,
+/// or stdin for `--input -`.
+///
+/// Exits with the exit code of a missing input if the input cannot be read.
+#[must_use]
+pub fn read_input(year: Year, day: Day) -> String {
+    let args: Vec<String> = env::args().collect();
+    let Some(path) = args
+        .iter()
+        .position(|x| x == "--input")
+        .and_then(|i| args.get(i + 1))
+    else {
+        return try_read_file("inputs", year, day).unwrap_or_else(|e| {
+            exit_with(&CommandError::MissingInput(format!(
+                "Could not read input \"{}\": {e}. Run `cargo download {day}` first.",
+                paths::day_file("inputs", year, day)
+            )))
+        });
+    };
+
+    let input = if path == "-" {
+        io::read_to_string(stdin())
+    } else {
+        fs::read_to_string(path)
+    };
+
+    input.unwrap_or_else(|e| {
+        exit_with(&CommandError::MissingInput(format!(
+            "Could not read input `{path}`: {e}"
+        )))
+    })
+}
+
+/// Exit the solution binary with the exit code that commands use for the same failure.
+fn exit_with(error: &CommandError) -> ! {
+    eprintln!("{error}");
+    process::exit(error.exit_code());
+}
+
+fn submit_usage_error() -> CommandError {
+    CommandError::Parse("Unexpected command-line input. Format: cargo solve 1 --submit 1".into())
+}
+
+/// Read the output format passed to the solution binary via `--format`, defaulting to text.
+fn output_format() -> OutputFormat {
+    let args: Vec<String> = env::args().collect();
+    args.iter()
+        .position(|x| x == "--format")
+        .and_then(|i| args.get(i + 1))
+        .and_then(|x| x.parse().ok())
+        .unwrap_or_default()
 }
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///
+/// When benched, the reported duration is the median of all samples.
 fn run_timed<I: Copy, T>(
     func: impl Fn(I) -> T,
     input: I,
+    is_timed: bool,
+    format: OutputFormat,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> (T, Duration, Option<BenchStats>, Option<HeapStats>) {
     let timer = Instant::now();
-    let result = {
-        #[cfg(feature = "dhat-heap")]
-        let _profiler = dhat::Profiler::new_heap();
-
-        func(input)
-    };
+    let (result, heap) = profile_heap(|| func(input));
     let base_time = timer.elapsed();
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    if is_timed {
+        let stats = bench(func, input, &base_time, format);
+        (result, stats.median, Some(stats), heap)
     } else {
-        (base_time, 1)
+        (result, base_time, None, heap)
+    }
+}
+
+/// Run `func` under the heap profiler when built with the `dhat-heap` feature.
+///
+/// With `--heap`, the heap stats of the call are returned instead of printing the `dhat`
+/// summary and writing a profile.
+#[cfg(feature = "dhat-heap")]
+fn profile_heap<T>(func: impl FnOnce() -> T) -> (T, Option<HeapStats>) {
+    if !env::args().any(|x| x == "--heap") {
+        let _profiler = dhat::Profiler::new_heap();
+        return (func(), None);
+    }
+
+    let _profiler = dhat::Profiler::builder().testing().build();
+    let result = func();
+    let stats = dhat::HeapStats::get();
+
+    let heap = HeapStats {
+        allocations: stats.total_blocks,
+        bytes: stats.total_bytes,
+        peak_bytes: stats.max_bytes as u64,
     };
 
-    (result, run.0, run.1)
+    (result, Some(heap))
+}
+
+#[cfg(not(feature = "dhat-heap"))]
+fn profile_heap<T>(func: impl FnOnce() -> T) -> (T, Option<HeapStats>) {
+    (func(), None)
 }
 
-fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
+/// Bench a solution part. A number of untimed warm-up iterations run first to
+/// settle caches and branch predictors before samples are collected.
+fn bench<I: Copy, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+    format: OutputFormat,
+) -> BenchStats {
+    if !format.is_json() {
+        let mut stdout = stdout();
+        print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+        let _ = stdout.flush();
+    }
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+    let budget = config::get().bench;
+    let bench_iterations = (budget.budget.as_nanos() / cmp::max(base_time.as_nanos(), 10))
+        .clamp(budget.min_samples, budget.max_samples);
 
-    let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+    let warmup_iterations = (bench_iterations / 10).max(1);
+
+    for _ in 0..warmup_iterations {
+        black_box(func(black_box(input)));
+    }
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -72,27 +509,34 @@ fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    BenchStats::from_samples(&timers).expect("at least one bench iteration is run")
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+fn format_duration(duration: &Duration, samples: u128, stats: Option<&BenchStats>) -> String {
+    match stats {
+        Some(stats) => format!(
+            " ({duration:.1?} ± {:.1?} @ {samples} samples)",
+            stats.std_dev
+        ),
+        None if samples == 1 => format!(" ({duration:.1?})"),
+        None => format!(" ({duration:.1?} @ {samples} samples)"),
+    }
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
-    } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
-    }
+fn print_stats(stats: &BenchStats) {
+    println!(
+        "  {ANSI_ITALIC}min {:.1?} · p95 {:.1?} · max {:.1?} · {} outliers{ANSI_RESET}",
+        stats.min, stats.p95, stats.max, stats.outliers
+    );
+}
+
+fn print_heap(heap: &HeapStats) {
+    println!(
+        "  {ANSI_ITALIC}{} allocations · {} allocated · {} peak{ANSI_RESET}",
+        heap.allocations,
+        memory::format_bytes(heap.bytes),
+        memory::format_bytes(heap.peak_bytes)
+    );
 }
 
 fn print_result<T: Display>(result: Option<&T>, part: &str, duration_str: &str) {
@@ -132,39 +576,68 @@ fn print_result<T: Display>(result: Option<&T>, part: &str, duration_str: &str)
 
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
+///  2. a session cookie is configured.
+///  3. the answer is not known to be wrong from earlier submissions.
+///
+/// Status messages go to stderr with `--format json`, so that stdout stays valid JSON lines.
 fn submit_result<T: Display>(
     result: T,
+    year: Year,
     day: Day,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+    format: OutputFormat,
+) -> Option<Result<aoc_client::Submission, CommandError>> {
     let args: Vec<String> = env::args().collect();
 
-    if !args.contains(&"--submit".into()) {
+    let part_index = args.iter().position(|x| x == "--submit")? + 1;
+
+    let Some(part_submit) = args.get(part_index).and_then(|x| x.parse::<u8>().ok()) else {
+        return Some(Err(submit_usage_error()));
+    };
+
+    if part_submit != part {
         return None;
     }
 
-    if args.len() < 3 {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
+    if args.contains(&"--input".into()) {
+        eprintln!("Refusing to submit: the answer was computed for a custom input.");
+        return None;
     }
 
-    let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
+    let answer = result.to_string();
 
-    let Ok(part_submit) = args[part_index].parse::<u8>() else {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
-    };
-
-    if part_submit != part {
+    if let Err(refusal) = Ledger::read_from_file(year).check(day, part, &answer, ledger::now()) {
+        eprintln!("Refusing to submit: {refusal}");
         return None;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+    print_status("Submitting result...", format);
+    let submission = aoc_client::submit(year, day, part, &answer)
+        .map_err(CommandError::aoc_client("Failed to submit answer"));
+
+    if let Ok(submission) = &submission {
+        print_status(&submission.message, format);
+
+        let entry = LedgerEntry::now(
+            day,
+            part,
+            &answer,
+            submission.verdict.clone(),
+            submission.wait_seconds,
+        );
+        if let Err(e) = entry.append_to_file(year) {
+            eprintln!("Failed to record submission: {e}");
+        }
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    Some(submission)
+}
+
+/// Print a status message, to stderr with JSON output.
+fn print_status(message: &str, format: OutputFormat) {
+    if format.is_json() {
+        eprintln!("{message}");
+    } else {
+        println!("{message}");
+    }
 }
@@ -0,0 +1,184 @@
+/// Summary statistics for benchmark samples.
+use std::{collections::HashMap, time::Duration};
+use tinyjson::JsonValue;
+
+/// Represents the distribution of a set of benchmark samples.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BenchStats {
+    pub samples: u128,
+    pub mean: Duration,
+    pub median: Duration,
+    pub min: Duration,
+    pub max: Duration,
+    pub std_dev: Duration,
+    pub p95: Duration,
+    /// Number of samples outside the Tukey fences (1.5 × IQR beyond the quartiles).
+    pub outliers: u128,
+}
+
+impl BenchStats {
+    /// Compute statistics for a set of samples, returns [`None`] if no samples are present.
+    #[must_use]
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+
+        let n = sorted.len();
+        let nanos: Vec<f64> = sorted.iter().map(|d| d.as_nanos() as f64).collect();
+
+        let mean = nanos.iter().sum::<f64>() / n as f64;
+        let variance = nanos.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n as f64;
+
+        let q1 = percentile(&nanos, 0.25);
+        let q3 = percentile(&nanos, 0.75);
+        let iqr = q3 - q1;
+        let (lower_fence, upper_fence) = (1.5_f64.mul_add(-iqr, q1), 1.5_f64.mul_add(iqr, q3));
+        let outliers = nanos
+            .iter()
+            .filter(|&&x| x < lower_fence || x > upper_fence)
+            .count();
+
+        Some(Self {
+            samples: n as u128,
+            mean: Duration::from_nanos(mean.round() as u64),
+            median: Duration::from_nanos(percentile(&nanos, 0.5).round() as u64),
+            min: sorted[0],
+            max: sorted[n - 1],
+            std_dev: Duration::from_nanos(variance.sqrt().round() as u64),
+            p95: Duration::from_nanos(percentile(&nanos, 0.95).round() as u64),
+            outliers: outliers as u128,
+        })
+    }
+}
+
+/// Linearly interpolated percentile of an ascending, non-empty slice.
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = p * (sorted.len() - 1) as f64;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+    let weight = rank - lower as f64;
+    (sorted[upper] - sorted[lower]).mul_add(weight, sorted[lower])
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&BenchStats> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &BenchStats) -> Self {
+        let mut map: HashMap<String, Self> = HashMap::new();
+
+        let nanos = |d: Duration| Self::Number(d.as_nanos() as f64);
+
+        map.insert("samples".into(), Self::Number(value.samples as f64));
+        map.insert("mean_nanos".into(), nanos(value.mean));
+        map.insert("median_nanos".into(), nanos(value.median));
+        map.insert("min_nanos".into(), nanos(value.min));
+        map.insert("max_nanos".into(), nanos(value.max));
+        map.insert("std_dev_nanos".into(), nanos(value.std_dev));
+        map.insert("p95_nanos".into(), nanos(value.p95));
+        map.insert("outliers".into(), Self::Number(value.outliers as f64));
+
+        Self::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for BenchStats {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected stats to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or_else(|| format!("Expected stats.{key} to be a number."))
+        };
+
+        let duration = |key: &str| number(key).map(|v| Duration::from_nanos(v as u64));
+
+        Ok(Self {
+            samples: number("samples")? as u128,
+            mean: duration("mean_nanos")?,
+            median: duration("median_nanos")?,
+            min: duration("min_nanos")?,
+            max: duration("max_nanos")?,
+            std_dev: duration("std_dev_nanos")?,
+            p95: duration("p95_nanos")?,
+            outliers: number("outliers")? as u128,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+    use tinyjson::JsonValue;
+
+    use super::BenchStats;
+
+    fn nanos(values: &[u64]) -> Vec<Duration> {
+        values.iter().copied().map(Duration::from_nanos).collect()
+    }
+
+    #[test]
+    fn handles_empty_samples() {
+        assert_eq!(BenchStats::from_samples(&[]), None);
+    }
+
+    #[test]
+    fn handles_single_sample() {
+        let stats = BenchStats::from_samples(&nanos(&[42])).unwrap();
+        assert_eq!(stats.samples, 1);
+        assert_eq!(stats.median, Duration::from_nanos(42));
+        assert_eq!(stats.min, Duration::from_nanos(42));
+        assert_eq!(stats.max, Duration::from_nanos(42));
+        assert_eq!(stats.std_dev, Duration::ZERO);
+        assert_eq!(stats.outliers, 0);
+    }
+
+    #[test]
+    fn computes_distribution() {
+        let stats = BenchStats::from_samples(&nanos(&[50, 10, 40, 20, 30])).unwrap();
+        assert_eq!(stats.samples, 5);
+        assert_eq!(stats.mean, Duration::from_nanos(30));
+        assert_eq!(stats.median, Duration::from_nanos(30));
+        assert_eq!(stats.min, Duration::from_nanos(10));
+        assert_eq!(stats.max, Duration::from_nanos(50));
+        assert_eq!(stats.std_dev, Duration::from_nanos(14));
+        assert_eq!(stats.p95, Duration::from_nanos(48));
+    }
+
+    #[test]
+    fn detects_outliers() {
+        let stats = BenchStats::from_samples(&nanos(&[10, 11, 10, 12, 11, 10, 500])).unwrap();
+        assert_eq!(stats.outliers, 1);
+        assert_eq!(stats.median, Duration::from_nanos(11));
+        assert_eq!(stats.max, Duration::from_nanos(500));
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let stats = BenchStats::from_samples(&nanos(&[10, 20, 30, 1000])).unwrap();
+        let json = JsonValue::from(&stats);
+        assert_eq!(BenchStats::try_from(&json).unwrap(), stats);
+    }
+}
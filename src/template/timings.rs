@@ -1,19 +1,51 @@
 use std::{collections::HashMap, fs, io::Error, str::FromStr};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::{memory::HeapStats, paths, record::Abort, stats::BenchStats, Day, Year};
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+static TIMINGS_FILE_NAME: &str = "timings.json";
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
 pub struct Timing {
     pub day: Day,
+    /// Time taken by the parse hook, for solutions that declare one.
+    pub parse: Option<String>,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
+    pub parse_stats: Option<BenchStats>,
+    pub part_1_stats: Option<BenchStats>,
+    pub part_2_stats: Option<BenchStats>,
+    /// Heap usage of the parts, only present for `cargo time --heap` runs.
+    pub parse_heap: Option<HeapStats>,
+    pub part_1_heap: Option<HeapStats>,
+    pub part_2_heap: Option<HeapStats>,
+    /// Parts that returned an error rather than no answer, [`PARSE_PART`](crate::template::PARSE_PART) for
+    /// the parse hook.
+    pub failed: Vec<u8>,
+    /// Set if the day was stopped by a limit before all of its parts finished.
+    pub aborted: Option<Abort>,
+    /// Peak resident memory of the day in bytes.
+    pub peak_rss: Option<u64>,
     pub total_nanos: f64,
 }
 
+impl Timing {
+    /// Whether a part returned an error in the timed run.
+    pub fn is_failed(&self, part: u8) -> bool {
+        self.failed.contains(&part)
+    }
+
+    /// Heap usage of the whole day, if it was measured.
+    pub fn heap(&self) -> Option<HeapStats> {
+        HeapStats::combine(
+            [self.parse_heap, self.part_1_heap, self.part_2_heap]
+                .into_iter()
+                .flatten(),
+        )
+    }
+}
+
 /// Represents benchmark times for a set of days.
 /// Can be serialized from / to JSON.
 #[derive(Clone, Debug, Default)]
@@ -23,26 +55,37 @@ pub struct Timings {
 
 impl Timings {
     /// Dehydrate timings to a JSON file.
-    pub fn store_file(&self) -> Result<(), Error> {
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        let mut file = fs::File::create(paths::data_file(year, TIMINGS_FILE_NAME))?;
         json.format_to(&mut file)
     }
 
     /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        fs::read_to_string(TIMINGS_FILE_PATH)
+    pub fn read_from_file(year: Year) -> Self {
+        fs::read_to_string(paths::data_file(year, TIMINGS_FILE_NAME))
             .map_err(|x| x.to_string())
             .and_then(Self::try_from)
             .unwrap_or_default()
     }
 
     /// Merge two sets of timings, overwriting `self` with `other` if present.
+    ///
+    /// Heap stats are only recorded by `cargo time --heap`, so the stored stats of a day are kept
+    /// when `other` did not measure them.
     pub fn merge(&self, new: &Self) -> Self {
         let mut data: Vec<Timing> = vec![];
 
         for timing in &new.data {
-            data.push(timing.clone());
+            let mut timing = timing.clone();
+            if timing.heap().is_none() {
+                if let Some(stored) = self.data.iter().find(|t| t.day == timing.day) {
+                    timing.parse_heap = stored.parse_heap;
+                    timing.part_1_heap = stored.part_1_heap;
+                    timing.part_2_heap = stored.part_2_heap;
+                }
+            }
+            data.push(timing);
         }
 
         for timing in &self.data {
@@ -51,15 +94,60 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.day);
         Self { data }
     }
 
+    /// Merge only the heap stats of `new` into `self`, keeping the stored durations.
+    ///
+    /// Parts run slower under the heap profiler, so the durations of a `--heap` run are not
+    /// comparable to those of a plain run. Days without stored timings only get heap stats.
+    pub fn merge_heap(&self, new: &Self) -> Self {
+        let mut merged = self.clone();
+
+        for timing in &new.data {
+            if let Some(stored) = merged.data.iter_mut().find(|t| t.day == timing.day) {
+                stored.parse_heap = timing.parse_heap;
+                stored.part_1_heap = timing.part_1_heap;
+                stored.part_2_heap = timing.part_2_heap;
+            } else {
+                merged.data.push(Timing {
+                    day: timing.day,
+                    parse: None,
+                    part_1: None,
+                    part_2: None,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse_heap: timing.parse_heap,
+                    part_1_heap: timing.part_1_heap,
+                    part_2_heap: timing.part_2_heap,
+                    failed: vec![],
+                    aborted: None,
+                    peak_rss: None,
+                    total_nanos: 0_f64,
+                });
+            }
+        }
+
+        merged.data.sort_unstable_by_key(|a| a.day);
+        merged
+    }
+
     /// Sum up total duration of timings as millis.
     pub fn total_millis(&self) -> f64 {
         self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
     }
 
+    /// Days where a part returned an error or that were aborted.
+    pub fn failed_days(&self) -> Vec<Day> {
+        self.data
+            .iter()
+            .filter(|t| !t.failed.is_empty() || t.aborted.is_some())
+            .map(|t| t.day)
+            .collect()
+    }
+
     pub fn is_day_complete(&self, day: Day) -> bool {
         self.data
             .iter()
@@ -108,19 +196,71 @@ impl TryFrom<String> for Timings {
 /* -------------------------------------------------------------------------- */
 
 impl From<&Timing> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
     fn from(value: &Timing) -> Self {
         let mut map: HashMap<String, Self> = HashMap::new();
 
         map.insert("day".into(), Self::String(value.day.to_string()));
         map.insert("total_nanos".into(), Self::Number(value.total_nanos));
 
+        let parse = value.parse.clone().map(Self::String);
         let part_1 = value.part_1.clone().map(Self::String);
         let part_2 = value.part_2.clone().map(Self::String);
 
+        map.insert("parse".into(), parse.map_or(Self::Null, |x| x));
+
         map.insert("part_1".into(), part_1.map_or(Self::Null, |x| x));
 
         map.insert("part_2".into(), part_2.map_or(Self::Null, |x| x));
 
+        map.insert(
+            "parse_stats".into(),
+            value.parse_stats.as_ref().map_or(Self::Null, Self::from),
+        );
+
+        map.insert(
+            "part_1_stats".into(),
+            value.part_1_stats.as_ref().map_or(Self::Null, Self::from),
+        );
+
+        map.insert(
+            "part_2_stats".into(),
+            value.part_2_stats.as_ref().map_or(Self::Null, Self::from),
+        );
+
+        for (key, heap) in [
+            ("parse_heap", &value.parse_heap),
+            ("part_1_heap", &value.part_1_heap),
+            ("part_2_heap", &value.part_2_heap),
+        ] {
+            map.insert(key.into(), heap.as_ref().map_or(Self::Null, Self::from));
+        }
+
+        map.insert(
+            "failed".into(),
+            Self::Array(
+                value
+                    .failed
+                    .iter()
+                    .map(|&part| Self::Number(f64::from(part)))
+                    .collect(),
+            ),
+        );
+
+        map.insert(
+            "aborted".into(),
+            value
+                .aborted
+                .map_or(Self::Null, |abort| Self::String(abort.to_string())),
+        );
+
+        map.insert(
+            "peak_rss".into(),
+            value
+                .peak_rss
+                .map_or(Self::Null, |bytes| Self::Number(bytes as f64)),
+        );
+
         Self::Object(map)
     }
 }
@@ -128,6 +268,7 @@ impl From<&Timing> for JsonValue {
 impl TryFrom<&JsonValue> for Timing {
     type Error = String;
 
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
     fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
         let json = value
             .get::<HashMap<String, JsonValue>>()
@@ -149,6 +290,51 @@ impl TryFrom<&JsonValue> for Timing {
             .map(|v| if v.is_null() { None } else { v.get::<String>() })
             .ok_or("Expected timing.part_2 to be null or string.")?;
 
+        // NOTE: the parse phase, failures, aborts, memory, heap and stats are optional to stay compatible with timings
+        // stored before they were recorded.
+        let parse = json
+            .get("parse")
+            .and_then(|v| if v.is_null() { None } else { v.get::<String>() });
+
+        let stats = |key: &str| match json.get(key) {
+            Some(v) if !v.is_null() => BenchStats::try_from(v).map(Some),
+            _ => Ok(None),
+        };
+
+        let parse_stats = stats("parse_stats")?;
+        let part_1_stats = stats("part_1_stats")?;
+        let part_2_stats = stats("part_2_stats")?;
+
+        let heap = |key: &str| match json.get(key) {
+            Some(v) if !v.is_null() => HeapStats::try_from(v).map(Some),
+            _ => Ok(None),
+        };
+
+        let parse_heap = heap("parse_heap")?;
+        let part_1_heap = heap("part_1_heap")?;
+        let part_2_heap = heap("part_2_heap")?;
+
+        let failed = json
+            .get("failed")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .map(|parts| {
+                parts
+                    .iter()
+                    .filter_map(|part| part.get::<f64>().map(|&part| part as u8))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let aborted = json
+            .get("aborted")
+            .and_then(|v| v.get::<String>())
+            .and_then(|abort| abort.parse().ok());
+
+        let peak_rss = json
+            .get("peak_rss")
+            .and_then(|v| v.get::<f64>())
+            .map(|&bytes| bytes as u64);
+
         let total_nanos = json
             .get("total_nanos")
             .and_then(|v| v.get::<f64>().copied())
@@ -156,8 +342,18 @@ impl TryFrom<&JsonValue> for Timing {
 
         Ok(Self {
             day,
+            parse: parse.cloned(),
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
+            parse_stats,
+            part_1_stats,
+            part_2_stats,
+            parse_heap,
+            part_1_heap,
+            part_2_heap,
+            failed,
+            aborted,
+            peak_rss,
             total_nanos,
         })
     }
@@ -176,20 +372,50 @@ mod tests {
             data: vec![
                 Timing {
                     day: day!(1),
+                    parse: None,
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse_heap: None,
+                    part_1_heap: None,
+                    part_2_heap: None,
+                    failed: vec![],
+                    aborted: None,
+                    peak_rss: None,
                     total_nanos: 3e+10,
                 },
                 Timing {
                     day: day!(2),
+                    parse: None,
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse_heap: None,
+                    part_1_heap: None,
+                    part_2_heap: None,
+                    failed: vec![],
+                    aborted: None,
+                    peak_rss: None,
                     total_nanos: 7e+10,
                 },
                 Timing {
                     day: day!(4),
+                    parse: None,
                     part_1: Some("40ms".into()),
                     part_2: None,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse_heap: None,
+                    part_1_heap: None,
+                    part_2_heap: None,
+                    failed: vec![],
+                    aborted: None,
+                    peak_rss: None,
                     total_nanos: 4e+10,
                 },
             ],
@@ -197,7 +423,10 @@ mod tests {
     }
 
     mod deserialization {
-        use crate::{day, template::timings::Timings};
+        use crate::{
+            day,
+            template::{record::Abort, timings::Timings},
+        };
 
         #[test]
         fn handles_json_timings() {
@@ -209,6 +438,29 @@ mod tests {
             assert_eq!(timing.part_1, Some("1ms".to_string()));
             assert_eq!(timing.part_2, None);
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
+            assert_eq!(timing.part_1_stats, None);
+        }
+
+        #[test]
+        fn handles_json_timings_with_stats() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000, "part_1_stats": { "samples": 10, "mean_nanos": 1100000, "median_nanos": 1000000, "min_nanos": 900000, "max_nanos": 2000000, "std_dev_nanos": 300000, "p95_nanos": 1800000, "outliers": 1 }, "part_2_stats": null }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            let stats = timing.part_1_stats.unwrap();
+            assert_eq!(stats.samples, 10);
+            assert_eq!(stats.median.as_nanos(), 1_000_000);
+            assert_eq!(stats.outliers, 1);
+            assert_eq!(timing.part_2_stats, None);
+        }
+
+        #[test]
+        fn handles_aborted_timings() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000, "aborted": "TIMEOUT" }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.aborted, Some(Abort::Timeout));
+            assert!(!timings.is_day_complete(day!(1)));
+            assert_eq!(timings.failed_days(), vec![day!(1)]);
         }
 
         #[test]
@@ -267,13 +519,23 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
+                    parse: None,
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse_heap: None,
+                    part_1_heap: None,
+                    part_2_heap: None,
+                    failed: vec![],
+                    aborted: None,
+                    peak_rss: None,
                     total_nanos: 3_000_000_000_f64,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -281,13 +543,23 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
+                    parse: None,
                     part_1: Some("1ms".into()),
                     part_2: None,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse_heap: None,
+                    part_1_heap: None,
+                    part_2_heap: None,
+                    failed: vec![],
+                    aborted: None,
+                    peak_rss: None,
                     total_nanos: 1_000_000_000_f64,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -295,20 +567,33 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
+                    parse: None,
                     part_1: None,
                     part_2: None,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse_heap: None,
+                    part_1_heap: None,
+                    part_2_heap: None,
+                    failed: vec![],
+                    aborted: None,
+                    peak_rss: None,
                     total_nanos: 0.0,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
     }
 
     mod merge {
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::{
+                memory::HeapStats,
+                timings::{Timing, Timings},
+            },
         };
 
         use super::get_mock_timings;
@@ -319,8 +604,18 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(3),
+                    parse: None,
                     part_1: None,
                     part_2: None,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse_heap: None,
+                    part_1_heap: None,
+                    part_2_heap: None,
+                    failed: vec![],
+                    aborted: None,
+                    peak_rss: None,
                     total_nanos: 0_f64,
                 }],
             };
@@ -339,8 +634,18 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(2),
+                    parse: None,
                     part_1: None,
                     part_2: None,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse_heap: None,
+                    part_1_heap: None,
+                    part_2_heap: None,
+                    failed: vec![],
+                    aborted: None,
+                    peak_rss: None,
                     total_nanos: 0_f64,
                 }],
             };
@@ -368,5 +673,43 @@ mod tests {
             let merged = timings.merge(&other);
             assert_eq!(merged.data.len(), 3);
         }
+
+        fn heap_stats(allocations: u64) -> Option<HeapStats> {
+            Some(HeapStats {
+                allocations,
+                bytes: 1024,
+                peak_bytes: 512,
+            })
+        }
+
+        #[test]
+        fn keeps_stored_durations_on_heap_runs() {
+            let timings = get_mock_timings();
+            let mut heap_run = get_mock_timings();
+            heap_run.data.truncate(1);
+            heap_run.data[0].part_1 = Some("900ms".into());
+            heap_run.data[0].total_nanos = 900_000_000_f64;
+            heap_run.data[0].part_1_heap = heap_stats(3);
+
+            let merged = timings.merge_heap(&heap_run);
+
+            assert_eq!(merged.data.len(), 3);
+            assert_eq!(merged.data[0].part_1, timings.data[0].part_1);
+            assert_eq!(merged.data[0].total_nanos, timings.data[0].total_nanos);
+            assert_eq!(merged.data[0].part_1_heap, heap_stats(3));
+        }
+
+        #[test]
+        fn keeps_stored_heap_stats_on_plain_runs() {
+            let mut timings = get_mock_timings();
+            timings.data[0].part_1_heap = heap_stats(3);
+            let mut plain_run = get_mock_timings();
+            plain_run.data[0].part_1 = Some("5ms".into());
+
+            let merged = timings.merge(&plain_run);
+
+            assert_eq!(merged.data[0].part_1, Some("5ms".into()));
+            assert_eq!(merged.data[0].part_1_heap, heap_stats(3));
+        }
     }
 }
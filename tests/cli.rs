@@ -0,0 +1,276 @@
+//! End-to-end tests of the commands, which run on a copy of the template in a temporary
+//! directory. Requests to the Advent of Code website are answered by a local fake server.
+#![cfg(feature = "test_lib")]
+
+use std::{
+    env, fs,
+    io::{BufRead, BufReader, Write},
+    net::{TcpListener, TcpStream},
+    path::{Path, PathBuf},
+    process::{self, Command, Output},
+    thread,
+};
+
+/// Files of the template copied into every project, relative to the crate root.
+const TEMPLATE_FILES: &[&str] = &[
+    "Cargo.toml",
+    "build.rs",
+    ".cargo/config.toml",
+    "src/lib.rs",
+    "src/main.rs",
+    "src/template.txt",
+];
+
+const INPUT: &str = "2\n3\n4\n";
+
+const PUZZLE: &str =
+    "<article class=\"day-desc\"><h2>--- Day 1: Fake ---</h2><p>Add the numbers.</p></article>";
+
+const SOLUTION: &str = r"advent_of_code::solution!(2021, 1);
+
+pub fn part_one(input: &str) -> Option<u64> {
+    Some(input.lines().map(|line| line.parse::<u64>().unwrap()).sum())
+}
+
+pub fn part_two(input: &str) -> Option<u64> {
+    Some(input.lines().map(|line| line.parse::<u64>().unwrap()).product())
+}
+";
+
+/// A copy of the template in a temporary directory, removed when dropped.
+struct Project {
+    root: PathBuf,
+    aoc: FakeAoc,
+}
+
+impl Project {
+    /// A project without solutions or data.
+    fn new(name: &str) -> Self {
+        let root = env::temp_dir().join(format!("aoc-cli-{name}-{}", process::id()));
+        let _ = fs::remove_dir_all(&root);
+
+        let crate_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+        for file in TEMPLATE_FILES {
+            copy(&crate_dir.join(file), &root.join(file));
+        }
+        // the lock file is not committed, but keeps the copy on the versions that are built.
+        if crate_dir.join("Cargo.lock").exists() {
+            copy(&crate_dir.join("Cargo.lock"), &root.join("Cargo.lock"));
+        }
+        copy_dir(&crate_dir.join("src/template"), &root.join("src/template"));
+        fs::create_dir_all(root.join("src/bin")).unwrap();
+
+        fs::write(
+            root.join("README.md"),
+            "# Advent of Code\n\n<!--- benchmarking table --->\n<!--- benchmarking table --->\n",
+        )
+        .unwrap();
+
+        Self {
+            root,
+            aoc: FakeAoc::start(),
+        }
+    }
+
+    /// A project with a solution and input for day 1.
+    fn with_day(name: &str) -> Self {
+        let project = Self::new(name);
+        project.write("src/bin/2021-01.rs", SOLUTION);
+        project.write("data/2021/inputs/01.txt", INPUT);
+        project.write("data/2021/examples/01.txt", "");
+        project
+    }
+
+    /// Run a command like its cargo alias does, from a directory inside the project.
+    fn run(&self, dir: &str, args: &[&str]) -> Output {
+        Command::new(env!("CARGO"))
+            .args(["run", "--quiet", "--"])
+            .args(args)
+            .current_dir(self.path(dir))
+            // share the build of the dependencies between runs of the tests.
+            .env(
+                "CARGO_TARGET_DIR",
+                Path::new(env!("CARGO_TARGET_TMPDIR")).join("cli"),
+            )
+            .env("CARGO_NET_OFFLINE", "true")
+            .env("AOC_BASE_URL", &self.aoc.url)
+            .env("AOC_SESSION", "fake-session")
+            .env_remove("AOC_ROOT")
+            .env_remove("AOC_CONFIG")
+            .env_remove("AOC_INPUT_KEY")
+            .output()
+            .unwrap()
+    }
+
+    fn path(&self, path: &str) -> PathBuf {
+        self.root.join(path)
+    }
+
+    fn read(&self, path: &str) -> String {
+        fs::read_to_string(self.path(path)).unwrap_or_default()
+    }
+
+    fn write(&self, path: &str, contents: &str) {
+        let path = self.path(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+}
+
+impl Drop for Project {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.root);
+    }
+}
+
+fn copy(from: &Path, to: &Path) {
+    fs::create_dir_all(to.parent().unwrap()).unwrap();
+    fs::copy(from, to).unwrap();
+}
+
+fn copy_dir(from: &Path, to: &Path) {
+    for entry in fs::read_dir(from).unwrap() {
+        let path = entry.unwrap().path();
+        let target = to.join(path.file_name().unwrap());
+
+        if path.is_dir() {
+            copy_dir(&path, &target);
+        } else {
+            copy(&path, &target);
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A fake Advent of Code website, which serves the same input and puzzle for every day.
+struct FakeAoc {
+    url: String,
+}
+
+impl FakeAoc {
+    fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                respond(stream);
+            }
+        });
+
+        Self { url }
+    }
+}
+
+fn respond(mut stream: TcpStream) {
+    let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).unwrap();
+
+    let mut line = String::new();
+    while reader.read_line(&mut line).unwrap() > 2 {
+        line.clear();
+    }
+
+    let path = request_line.split(' ').nth(1).unwrap_or_default();
+    let body = if path.ends_with("/input") {
+        INPUT
+    } else {
+        PUZZLE
+    };
+
+    write!(
+        stream,
+        "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )
+    .unwrap();
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn assert_success(output: &Output) {
+    assert!(
+        output.status.success(),
+        "command exited with {}.\nstdout:\n{}\nstderr:\n{}",
+        output.status,
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+#[test]
+fn scaffolds_a_day() {
+    let project = Project::new("scaffold");
+
+    let output = project.run(".", &["scaffold", "1"]);
+    assert_success(&output);
+    assert!(project
+        .read("src/bin/2021-01.rs")
+        .starts_with("advent_of_code::solution!(2021, 1);"));
+    assert!(project.path("data/2021/inputs/01.txt").exists());
+    assert!(project.path("data/2021/examples/01.txt").exists());
+}
+
+#[test]
+fn downloads_input_and_puzzle() {
+    let project = Project::new("download");
+
+    let output = project.run(".", &["download", "1"]);
+    assert_success(&output);
+    assert_eq!(project.read("data/2021/inputs/01.txt"), INPUT);
+    assert!(project
+        .read("data/2021/puzzles/01.md")
+        .contains("Day 1: Fake"));
+}
+
+#[test]
+fn solves_a_day_from_a_subdirectory() {
+    let project = Project::with_day("solve");
+
+    let output = project.run("src/bin", &["solve", "1", "--format", "json"]);
+    assert_success(&output);
+    assert!(stdout(&output).contains("\"answer\":\"9\""));
+    assert!(stdout(&output).contains("\"answer\":\"24\""));
+
+    let output = project.run("data", &["solve", "2"]);
+    assert_eq!(output.status.code(), Some(3));
+}
+
+#[test]
+fn runs_all_days() {
+    let project = Project::with_day("all");
+
+    let output = project.run(".", &["all", "--format", "json"]);
+    assert_success(&output);
+    assert!(stdout(&output).contains("\"answer\":\"9\""));
+    assert!(stdout(&output).contains("\"answer\":\"24\""));
+}
+
+#[test]
+fn stores_timings() {
+    let project = Project::with_day("time");
+
+    let output = project.run(
+        "data/2021",
+        &["time", "1", "--store", "--config", "bench.budget=0.01"],
+    );
+    assert_success(&output);
+    assert!(project.read("data/2021/timings.json").contains("\"01\""));
+    assert_eq!(
+        project
+            .read("data/2021/timings-history.jsonl")
+            .lines()
+            .count(),
+        1
+    );
+    assert!(project
+        .read("README.md")
+        .contains("| [Day 1](./src/bin/2021-01.rs) |"));
+}